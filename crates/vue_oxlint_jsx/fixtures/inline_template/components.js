@@ -0,0 +1,58 @@
+import Vue from 'vue'
+import { createApp, defineComponent } from 'vue'
+import Card from './Card.vue'
+
+const Counter = defineComponent({
+  props: ['step'],
+  data: () => ({ count: 0 }),
+  methods: {
+    increment() {
+      this.count += this.step
+    },
+  },
+  template: `
+    <div class="counter">
+      <!-- the current count -->
+      <span>{{ count }}</span>
+      <button @click="increment" v-if="step > 0">+{{ step }}</button>
+    </div>
+  `,
+})
+
+Vue.component('greeting', {
+  props: { name: String },
+  components: {
+    Badge: {
+      props: ['label'],
+      template: '<b>{{ label }}</b>',
+    },
+  },
+  template: '<p>Hello, <Badge :label="name" /> and <Card /></p>',
+})
+
+new Vue({
+  el: '#app',
+  delimiters: ['${', '}'],
+  data: { items: ['a', 'b'] },
+  template: '<ul><li v-for="item in items" :key="item">${ item }</li></ul>',
+})
+
+createApp({
+  template: '#app-template',
+})
+
+const name = 'dynamic'
+Vue.extend({
+  template: `<p>${name}</p>`,
+})
+
+const root = createApp({})
+root.component('item', {
+  props: ['text', 'item-title'],
+  template: '<li :title="itemTitle">{{ text }}</li>',
+})
+
+// Not a Vue app
+registry.component('row', {
+  template: '<tr>{{ cells }}</tr>',
+})
@@ -0,0 +1,6 @@
+import { defineComponent } from 'vue'
+
+export default defineComponent({
+  setup: () => ({ msg: 'hi' as string }),
+  template: '<p v-if="">{{ msg + }}</p>',
+})
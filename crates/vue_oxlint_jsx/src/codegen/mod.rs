@@ -1,10 +1,15 @@
+use std::sync::Arc;
+
 use oxc_allocator::Allocator;
 use oxc_ast::Comment;
 use oxc_diagnostics::OxcDiagnostic;
 use oxc_parser::ParseOptions;
 use oxc_span::{SourceType, Span};
 
-use crate::parser::{ParseConfig, ParserImpl};
+use crate::parser::{
+  CustomBlockHandlers, CustomElements, Delimiters, ParseConfig, ParserImpl, SourceResolver,
+  VirtualDocument, VueVersion,
+};
 
 #[allow(
   clippy::branches_sharing_code,
@@ -34,6 +39,8 @@ pub struct VueJsxCodegenReturn {
   pub irregular_whitespaces: Box<[Span]>,
   /// Generated source ranges mapped back to original Vue SFC source ranges.
   pub mappings: Vec<Mapping>,
+  /// The `<style>` and custom blocks as virtual documents, in source order.
+  pub virtual_documents: Vec<VirtualDocument>,
   /// Diagnostics produced while parsing the Vue SFC.
   pub errors: Vec<OxcDiagnostic>,
   /// `true` if parsing fatally failed; [`VueJsxCodegenReturn::source_text`]
@@ -64,12 +71,25 @@ pub struct VueJsxCodegenReturn {
 pub struct VueJsxCodegen<'a> {
   source_text: &'a str,
   options: ParseOptions,
+  vue_version: VueVersion,
+  custom_elements: CustomElements,
+  delimiters: Delimiters,
+  custom_block_handlers: CustomBlockHandlers,
+  source_resolver: Option<Arc<dyn SourceResolver>>,
 }
 
 impl<'a> VueJsxCodegen<'a> {
   #[must_use]
   pub fn new(source_text: &'a str) -> Self {
-    Self { source_text, options: ParseOptions::default() }
+    Self {
+      source_text,
+      options: ParseOptions::default(),
+      vue_version: VueVersion::default(),
+      custom_elements: CustomElements::new(),
+      delimiters: Delimiters::default(),
+      custom_block_handlers: CustomBlockHandlers::new(),
+      source_resolver: None,
+    }
   }
 
   /// Overrides the [`ParseOptions`] passed to the underlying `oxc_parser`.
@@ -79,13 +99,64 @@ impl<'a> VueJsxCodegen<'a> {
     self
   }
 
+  /// Selects the Vue version whose template semantics (e.g. `v-if` / `v-for` precedence) the
+  /// generated code follows. Defaults to [`VueVersion::V3`].
+  #[must_use]
+  pub const fn with_vue_version(mut self, vue_version: VueVersion) -> Self {
+    self.vue_version = vue_version;
+    self
+  }
+
+  /// Sets the tags which are custom elements (web components), like Vue's
+  /// `compilerOptions.isCustomElement`. They are kept as intrinsic elements.
+  #[must_use]
+  pub fn with_custom_elements(mut self, custom_elements: CustomElements) -> Self {
+    self.custom_elements = custom_elements;
+    self
+  }
+
+  /// Sets the interpolation delimiters, like Vue's `compilerOptions.delimiters`. Defaults to `{{`
+  /// and `}}`. A component's own `delimiters` option takes precedence.
+  #[must_use]
+  pub fn with_delimiters(mut self, delimiters: Delimiters) -> Self {
+    self.delimiters = delimiters;
+    self
+  }
+
+  /// Sets the handlers checking custom blocks like `<i18n>`, which may also declare objects in
+  /// the generated code.
+  #[must_use]
+  pub fn with_custom_block_handlers(mut self, custom_block_handlers: CustomBlockHandlers) -> Self {
+    self.custom_block_handlers = custom_block_handlers;
+    self
+  }
+
+  /// Sets the [`SourceResolver`] reading the `src` of `<script src>` and `<template src>`, and
+  /// recording the one of `<style src>`. The generated content of those files has no mappings.
+  #[must_use]
+  pub fn with_source_resolver(mut self, source_resolver: Arc<dyn SourceResolver>) -> Self {
+    self.source_resolver = Some(source_resolver);
+    self
+  }
+
   /// Parses the Vue SFC and runs `oxc_codegen` to produce JS/TS source.
   #[must_use]
   pub fn build(self) -> VueJsxCodegenReturn {
     let allocator = Allocator::default();
-    let ret =
-      ParserImpl::new(&allocator, self.source_text, self.options, ParseConfig { codegen: true })
-        .parse();
+    let ret = ParserImpl::new(
+      &allocator,
+      self.source_text,
+      self.options,
+      ParseConfig {
+        codegen: true,
+        vue_version: self.vue_version,
+        custom_elements: self.custom_elements,
+        delimiters: self.delimiters,
+        custom_block_handlers: self.custom_block_handlers,
+        source_resolver: self.source_resolver,
+      },
+    )
+    .parse();
 
     if ret.fatal {
       return VueJsxCodegenReturn {
@@ -94,6 +165,7 @@ impl<'a> VueJsxCodegen<'a> {
         comments: Vec::new(),
         irregular_whitespaces: Box::new([]),
         mappings: Vec::new(),
+        virtual_documents: ret.virtual_documents,
         errors: ret.errors,
         panicked: true,
       };
@@ -103,13 +175,21 @@ impl<'a> VueJsxCodegen<'a> {
     let source_text = codegen_ret.code;
     let source_type = ret.program.source_type;
     let comments = ret.program.comments.iter().copied().collect();
+    // The nodes from external files are in their own span space, past the end of the SFC
+    let sfc_end = self.source_text.len() as u32;
+    let mappings = codegen_ret
+      .mappings
+      .into_iter()
+      .filter(|mapping| mapping.original_span.end <= sfc_end)
+      .collect();
 
     VueJsxCodegenReturn {
       source_text,
       source_type,
       comments,
       irregular_whitespaces: ret.irregular_whitespaces,
-      mappings: codegen_ret.mappings,
+      mappings,
+      virtual_documents: ret.virtual_documents,
       errors: ret.errors,
       panicked: false,
     }
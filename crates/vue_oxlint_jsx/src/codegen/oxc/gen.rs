@@ -103,6 +103,20 @@ impl Gen for Directive<'_> {
 }
 
 impl Gen for Statement<'_> {
+  fn print(&self, p: &mut Codegen, ctx: Context) {
+    if p.try_emit_clean(self.span()) {
+      // `function f() {}`, `interface X {}` etc. are already terminated by their body, a `;`
+      // separator would be re-parsed as an extra `EmptyStatement`.
+      if p.last_byte() == Some(b'}') && ends_with_block_body(self) {
+        p.needs_semicolon = false;
+      }
+      return;
+    }
+    p.enter_mapping(self.span());
+    self.r#gen(p, ctx);
+    p.leave_mapping();
+  }
+
   fn r#gen(&self, p: &mut Codegen, ctx: Context) {
     match self {
       // Most common statements first (based on parser order and frequency)
@@ -167,6 +181,48 @@ impl Gen for Statement<'_> {
   }
 }
 
+/// Whether `stmt` is terminated by a trailing block body rather than by a `;`.
+fn ends_with_block_body(stmt: &Statement<'_>) -> bool {
+  match stmt {
+    Statement::BlockStatement(_)
+    | Statement::FunctionDeclaration(_)
+    | Statement::ClassDeclaration(_)
+    | Statement::TryStatement(_)
+    | Statement::SwitchStatement(_)
+    | Statement::TSInterfaceDeclaration(_)
+    | Statement::TSEnumDeclaration(_)
+    | Statement::TSModuleDeclaration(_)
+    | Statement::TSGlobalDeclaration(_) => true,
+    Statement::IfStatement(stmt) => {
+      ends_with_block_body(stmt.alternate.as_ref().unwrap_or(&stmt.consequent))
+    }
+    Statement::ForStatement(stmt) => ends_with_block_body(&stmt.body),
+    Statement::ForInStatement(stmt) => ends_with_block_body(&stmt.body),
+    Statement::ForOfStatement(stmt) => ends_with_block_body(&stmt.body),
+    Statement::WhileStatement(stmt) => ends_with_block_body(&stmt.body),
+    Statement::WithStatement(stmt) => ends_with_block_body(&stmt.body),
+    Statement::LabeledStatement(stmt) => ends_with_block_body(&stmt.body),
+    Statement::ExportNamedDeclaration(decl) => decl.declaration.as_ref().is_some_and(|decl| {
+      matches!(
+        decl,
+        Declaration::FunctionDeclaration(_)
+          | Declaration::ClassDeclaration(_)
+          | Declaration::TSInterfaceDeclaration(_)
+          | Declaration::TSEnumDeclaration(_)
+          | Declaration::TSModuleDeclaration(_)
+          | Declaration::TSGlobalDeclaration(_)
+      )
+    }),
+    Statement::ExportDefaultDeclaration(decl) => matches!(
+      decl.declaration,
+      ExportDefaultDeclarationKind::FunctionDeclaration(_)
+        | ExportDefaultDeclarationKind::ClassDeclaration(_)
+        | ExportDefaultDeclarationKind::TSInterfaceDeclaration(_)
+    ),
+    _ => false,
+  }
+}
+
 impl Gen for ExpressionStatement<'_> {
   fn r#gen(&self, p: &mut Codegen, _ctx: Context) {
     p.start_of_stmt = p.code_len();
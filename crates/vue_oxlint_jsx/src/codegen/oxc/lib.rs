@@ -51,6 +51,7 @@ pub struct Mapping {
 }
 
 impl Mapping {
+  #[must_use]
   pub fn new(codegen_span: Span, original_span: Span) -> Self {
     Self { codegen_span, original_span }
   }
@@ -331,7 +332,7 @@ impl<'a> Codegen<'a> {
     self.mappings.push(Mapping::new(Span::new(codegen_start, codegen_end), span));
     // If the clean text already ends with `;` no extra separator is needed.
     // In all other cases (including `}`) signal that a `;` should precede the next statement.
-    // Function/class declarations that end with `}` will harmlessly get a trailing `;\n`.
+    // Statements terminated by a block body (e.g. `function f() {}`) reset this in `Statement::print`.
     self.needs_semicolon = self.last_byte() != Some(b';');
     true
   }
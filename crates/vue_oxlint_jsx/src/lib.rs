@@ -1,8 +1,23 @@
 mod codegen;
+mod manifest;
+mod markdown;
 mod parser;
 
 #[cfg(test)]
 mod test;
 
-pub use crate::codegen::{VueJsxCodegen, VueJsxCodegenReturn};
-pub use crate::parser::{VueJsxParser, VueJsxParserReturn};
+pub use crate::codegen::{Mapping, VueJsxCodegen, VueJsxCodegenReturn};
+pub use crate::manifest::{
+  ComponentManifest, EmitManifest, ExposeManifest, ModelManifest, PropManifest, SlotManifest,
+  SlotPropManifest,
+};
+pub use crate::markdown::{MarkdownMapping, MarkdownSfc};
+pub use crate::parser::{
+  ComponentDefinition, ComponentMember, ComponentMemberKind, CustomBlock, CustomBlockAttribute,
+  CustomBlockHandler, CustomBlockHandlers, CustomBlockReturn, CustomElements, Delimiters,
+  ExternalSource, FileDiagnostic, FileId, FsSourceResolver, I18nBlockHandler, InlineTemplate,
+  InlineTemplates, InlineTemplatesReturn, LocalRegistration, MemorySourceResolver,
+  OptionsApiComponent, SourceResolver, SyntheticDeclaration, ThisReference, VirtualDocument,
+  VueJsxParser, VueJsxParserReturn, VueScriptSection, VueScriptsReturn, VueVersion,
+  YamlBlockHandler,
+};
@@ -0,0 +1,225 @@
+//! Component metadata manifest, for design-system documentation and cross-file checks.
+//!
+//! The manifest is built from the [`Program`] returned by [`crate::VueJsxParser`], reading both
+//! `<script setup>` macros (`defineProps`, `defineEmits`, `defineModel`, `defineSlots`,
+//! `defineExpose`, `defineOptions`) and the Options API (`export default { ... }`), plus the
+//! `<slot>` outlets of the template. Descriptions come from the `JSDoc` (or HTML comment for
+//! slots) directly preceding each declaration.
+
+use oxc_ast::{
+  Comment,
+  ast::{Expression, Program, Statement},
+};
+use oxc_span::Span;
+use serde::Serialize;
+
+mod script;
+mod template;
+
+/// The public interface of a single-file component.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentManifest {
+  /// The `name` option, from `defineOptions({ name })` or Options API `name`.
+  pub name: Option<String>,
+  pub props: Vec<PropManifest>,
+  pub emits: Vec<EmitManifest>,
+  pub slots: Vec<SlotManifest>,
+  pub models: Vec<ModelManifest>,
+  pub exposes: Vec<ExposeManifest>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropManifest {
+  pub name: String,
+  /// Source text of the TypeScript type or the runtime `type` constructor.
+  pub r#type: Option<String>,
+  pub required: bool,
+  /// Source text of the default value.
+  pub default: Option<String>,
+  pub description: Option<String>,
+  pub start: u32,
+  pub end: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitManifest {
+  pub name: String,
+  /// Source text of each payload parameter, e.g. `id: number`.
+  pub payload: Vec<String>,
+  pub description: Option<String>,
+  pub start: u32,
+  pub end: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotManifest {
+  /// Slot name, or `[expression]` for a dynamic `:name`.
+  pub name: String,
+  /// Props bound on the `<slot>` outlets.
+  pub props: Vec<SlotPropManifest>,
+  /// Source text of the slot props type declared with `defineSlots`.
+  pub r#type: Option<String>,
+  pub description: Option<String>,
+  pub start: u32,
+  pub end: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotPropManifest {
+  pub name: String,
+  /// Source text of the bound expression.
+  pub expression: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelManifest {
+  /// The `v-model` argument, `modelValue` for a plain `v-model`.
+  pub name: String,
+  pub r#type: Option<String>,
+  pub description: Option<String>,
+  pub start: u32,
+  pub end: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExposeManifest {
+  pub name: String,
+  pub description: Option<String>,
+  pub start: u32,
+  pub end: u32,
+}
+
+impl ComponentManifest {
+  /// Build the manifest of a Vue SFC from the program produced by [`crate::VueJsxParser`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use oxc_allocator::Allocator;
+  /// use vue_oxlint_jsx::{ComponentManifest, VueJsxParser};
+  ///
+  /// let allocator = Allocator::default();
+  /// let source = r#"<script setup lang="ts">
+  /// defineProps<{ msg: string }>();
+  /// </script>"#;
+  ///
+  /// let ret = VueJsxParser::new(&allocator, source).parse();
+  /// let manifest = ComponentManifest::from_program(&ret.program);
+  /// assert_eq!(manifest.props[0].name, "msg");
+  /// ```
+  #[must_use]
+  pub fn from_program(program: &Program<'_>) -> Self {
+    let mut builder = ManifestBuilder {
+      source_text: program.source_text,
+      comments: &program.comments,
+      manifest: Self::default(),
+    };
+
+    let (global, setup) = split_program(program);
+    builder.analyze_scripts(global, setup);
+    if let Some(template) = setup.last() {
+      builder.analyze_template(template);
+    }
+    builder.derive_models();
+
+    builder.manifest
+  }
+}
+
+struct ManifestBuilder<'m> {
+  source_text: &'m str,
+  comments: &'m [Comment],
+  manifest: ComponentManifest,
+}
+
+impl ManifestBuilder<'_> {
+  fn text(&self, span: Span) -> String {
+    span.source_text(self.source_text).to_string()
+  }
+
+  /// The `JSDoc` block (or HTML comment) directly preceding `start`.
+  fn description(&self, start: u32) -> Option<String> {
+    let index = self.comments.partition_point(|comment| comment.span.end <= start);
+    let comment = self.comments[..index].last()?;
+    let text = comment.span.source_text(self.source_text);
+    let gap = &self.source_text[comment.span.end as usize..start as usize];
+
+    let is_html = self.source_text[..comment.span.start as usize].ends_with("<!--");
+    let content = if is_html {
+      if !gap.strip_prefix("-->")?.trim().is_empty() {
+        return None;
+      }
+      text
+    } else {
+      if !gap.trim().is_empty() {
+        return None;
+      }
+      text.strip_prefix("/**")?.strip_suffix("*/")?
+    };
+
+    let description = content
+      .lines()
+      .map(|line| {
+        let line = line.trim();
+        line.strip_prefix('*').map_or(line, str::trim_start)
+      })
+      .collect::<Vec<_>>()
+      .join("\n");
+    let description = description.trim();
+    (!description.is_empty()).then(|| description.to_string())
+  }
+
+  /// `v-model` bindings are props with a matching `update:xxx` emit.
+  fn derive_models(&mut self) {
+    let ComponentManifest { props, emits, models, .. } = &mut self.manifest;
+    for prop in props.iter() {
+      let event = format!("update:{}", prop.name);
+      if emits.iter().any(|emit| emit.name == event)
+        && !models.iter().any(|model| model.name == prop.name)
+      {
+        models.push(ModelManifest {
+          name: prop.name.clone(),
+          r#type: prop.r#type.clone(),
+          description: prop.description.clone(),
+          start: prop.start,
+          end: prop.end,
+        });
+      }
+    }
+  }
+}
+
+/// Split the program into the top-level `<script>` statements and the `<script setup>` arrow
+/// body, whose last statement is the SFC structure fragment.
+fn split_program<'p, 'a>(program: &'p Program<'a>) -> (&'p [Statement<'a>], &'p [Statement<'a>]) {
+  if let Some((Statement::ExpressionStatement(stmt), global)) = program.body.split_last()
+    && let Expression::ArrowFunctionExpression(arrow) = &stmt.expression
+  {
+    (global, &arrow.body.statements)
+  } else {
+    (&program.body, &[])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_manifest;
+
+  #[test]
+  fn setup() {
+    test_manifest!("manifest/setup.vue");
+    test_manifest!("manifest/setup_runtime.vue");
+  }
+
+  #[test]
+  fn options_api() {
+    test_manifest!("manifest/options_api.vue");
+  }
+}
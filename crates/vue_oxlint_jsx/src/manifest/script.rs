@@ -0,0 +1,416 @@
+use oxc_ast::ast::{
+  Argument, ArrayExpressionElement, BindingPattern, CallExpression, Declaration, Expression,
+  FormalParameters, ObjectExpression, ObjectPropertyKind, Statement, TSLiteral, TSSignature,
+  TSType, TSTypeName,
+};
+use oxc_span::{GetSpan, Span};
+use rustc_hash::FxHashMap;
+
+use crate::manifest::{
+  EmitManifest, ExposeManifest, ManifestBuilder, ModelManifest, PropManifest, SlotManifest,
+};
+use crate::parser::options_api::{component_options, key_name};
+
+/// Interfaces and object type aliases declared in the scripts, used to resolve
+/// `defineProps<Props>()` style type references.
+type TypeScope<'p, 'a> = FxHashMap<&'a str, &'p [TSSignature<'a>]>;
+
+impl ManifestBuilder<'_> {
+  pub(super) fn analyze_scripts<'p, 'a>(
+    &mut self,
+    global: &'p [Statement<'a>],
+    setup: &'p [Statement<'a>],
+  ) {
+    // The last statement of the setup arrow is the SFC structure fragment
+    let setup = setup.split_last().map_or(setup, |(_, statements)| statements);
+
+    let mut types = TypeScope::default();
+    collect_types(global, &mut types);
+    collect_types(setup, &mut types);
+
+    for statement in global {
+      if let Statement::ExportDefaultDeclaration(decl) = statement
+        && let Some((_, options)) = decl.declaration.as_expression().and_then(component_options)
+      {
+        self.analyze_options(options);
+      }
+    }
+
+    for statement in setup {
+      match statement {
+        Statement::ExpressionStatement(stmt) => self.analyze_macro(&stmt.expression, None, &types),
+        Statement::VariableDeclaration(decl) => {
+          for declarator in &decl.declarations {
+            if let Some(init) = &declarator.init {
+              self.analyze_macro(init, Some(&declarator.id), &types);
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn analyze_options(&mut self, options: &ObjectExpression) {
+    for property in &options.properties {
+      let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+      let value = property.value.get_inner_expression();
+      match key_name(&property.key) {
+        Some("name") => self.set_name(value),
+        Some("props") => self.runtime_props(value),
+        Some("emits") => self.runtime_emits(value),
+        Some("expose") => {
+          for (name, span) in string_elements(value) {
+            self.push_expose(name, span);
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn analyze_macro<'a>(
+    &mut self,
+    expression: &Expression<'a>,
+    id: Option<&BindingPattern<'a>>,
+    types: &TypeScope<'_, 'a>,
+  ) {
+    let Expression::CallExpression(call) = expression.get_inner_expression() else { return };
+    let Expression::Identifier(callee) = &call.callee else { return };
+
+    match callee.name.as_str() {
+      "defineProps" => self.define_props(call, types),
+      "withDefaults" => {
+        if let Some(Expression::CallExpression(props)) =
+          call.arguments.first().and_then(Argument::as_expression)
+          && props.callee.is_specific_id("defineProps")
+        {
+          self.define_props(props, types);
+          if let Some(Argument::ObjectExpression(defaults)) = call.arguments.get(1) {
+            for property in &defaults.properties {
+              let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+              if let Some(name) = key_name(&property.key) {
+                let default = self.text(property.value.span());
+                self.set_prop_default(name, default);
+              }
+            }
+          }
+        }
+      }
+      "defineEmits" => self.define_emits(call, types),
+      "defineModel" => self.define_model(call),
+      "defineSlots" => self.define_slots(call, types),
+      "defineExpose" => {
+        if let Some(Argument::ObjectExpression(object)) = call.arguments.first() {
+          for property in &object.properties {
+            let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+            if let Some(name) = key_name(&property.key) {
+              self.push_expose(name, property.key.span());
+            }
+          }
+        }
+      }
+      "defineOptions" => {
+        if let Some(Argument::ObjectExpression(object)) = call.arguments.first() {
+          self.analyze_options(object);
+        }
+      }
+      _ => return,
+    }
+
+    // Reactive props destructure: `const { size = 'md' } = defineProps<Props>()`
+    if let Some(BindingPattern::ObjectPattern(pattern)) = id {
+      for property in &pattern.properties {
+        if let BindingPattern::AssignmentPattern(assignment) = &property.value
+          && let Some(name) = key_name(&property.key)
+        {
+          let default = self.text(assignment.right.span());
+          self.set_prop_default(name, default);
+        }
+      }
+    }
+  }
+
+  fn define_props<'a>(&mut self, call: &CallExpression<'a>, types: &TypeScope<'_, 'a>) {
+    if let Some(members) = type_argument_members(call, types) {
+      for member in members {
+        let TSSignature::TSPropertySignature(signature) = member else { continue };
+        let Some(name) = key_name(&signature.key) else { continue };
+        self.manifest.props.push(PropManifest {
+          name: name.to_string(),
+          r#type: signature.type_annotation.as_ref().map(|ty| self.text(ty.type_annotation.span())),
+          required: !signature.optional,
+          default: None,
+          description: self.description(signature.span.start),
+          start: signature.span.start,
+          end: signature.span.end,
+        });
+      }
+    } else if let Some(argument) = call.arguments.first().and_then(Argument::as_expression) {
+      self.runtime_props(argument.get_inner_expression());
+    }
+  }
+
+  /// `['a', 'b']` or `{ a: String, b: { type: Number, required: true, default: 0 } }`
+  fn runtime_props(&mut self, value: &Expression) {
+    if let Expression::ObjectExpression(object) = value {
+      for property in &object.properties {
+        let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+        let Some(name) = key_name(&property.key) else { continue };
+
+        let mut prop = PropManifest {
+          name: name.to_string(),
+          r#type: None,
+          required: false,
+          default: None,
+          description: self.description(property.span.start),
+          start: property.span.start,
+          end: property.span.end,
+        };
+        match property.value.get_inner_expression() {
+          Expression::ObjectExpression(options) => {
+            for option in &options.properties {
+              let ObjectPropertyKind::ObjectProperty(option) = option else { continue };
+              match key_name(&option.key) {
+                Some("type") => prop.r#type = Some(self.text(option.value.span())),
+                Some("required") => {
+                  prop.required =
+                    matches!(&option.value, Expression::BooleanLiteral(lit) if lit.value);
+                }
+                Some("default") => prop.default = Some(self.text(option.value.span())),
+                _ => {}
+              }
+            }
+          }
+          value => prop.r#type = Some(self.text(value.span())),
+        }
+        self.manifest.props.push(prop);
+      }
+    } else {
+      for (name, span) in string_elements(value) {
+        self.manifest.props.push(PropManifest {
+          name: name.to_string(),
+          r#type: None,
+          required: false,
+          default: None,
+          description: self.description(span.start),
+          start: span.start,
+          end: span.end,
+        });
+      }
+    }
+  }
+
+  fn define_emits<'a>(&mut self, call: &CallExpression<'a>, types: &TypeScope<'_, 'a>) {
+    let Some(members) = type_argument_members(call, types) else {
+      if let Some(argument) = call.arguments.first().and_then(Argument::as_expression) {
+        self.runtime_emits(argument.get_inner_expression());
+      }
+      return;
+    };
+
+    for member in members {
+      let (name, payload) = match member {
+        // `(e: 'change', id: number): void`
+        TSSignature::TSCallSignatureDeclaration(signature) => {
+          let Some((event, rest)) = signature.params.items.split_first() else { continue };
+          let Some(TSType::TSLiteralType(literal)) =
+            event.type_annotation.as_ref().map(|ty| &ty.type_annotation)
+          else {
+            continue;
+          };
+          let TSLiteral::StringLiteral(name) = &literal.literal else { continue };
+          (name.value.as_str(), rest.iter().map(|param| self.text(param.span)).collect())
+        }
+        // `change: [id: number]`
+        TSSignature::TSPropertySignature(signature) => {
+          let Some(name) = key_name(&signature.key) else { continue };
+          let payload = match signature.type_annotation.as_ref().map(|ty| &ty.type_annotation) {
+            Some(TSType::TSTupleType(tuple)) => {
+              tuple.element_types.iter().map(|element| self.text(element.span())).collect()
+            }
+            _ => vec![],
+          };
+          (name, payload)
+        }
+        _ => continue,
+      };
+      self.manifest.emits.push(EmitManifest {
+        name: name.to_string(),
+        payload,
+        description: self.description(member.span().start),
+        start: member.span().start,
+        end: member.span().end,
+      });
+    }
+  }
+
+  /// `['change']` or `{ change: (id: number) => true, submit: null }`
+  fn runtime_emits(&mut self, value: &Expression) {
+    if let Expression::ObjectExpression(object) = value {
+      for property in &object.properties {
+        let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+        let Some(name) = key_name(&property.key) else { continue };
+        let payload = match property.value.get_inner_expression() {
+          Expression::ArrowFunctionExpression(arrow) => self.params(&arrow.params),
+          Expression::FunctionExpression(function) => self.params(&function.params),
+          _ => vec![],
+        };
+        self.manifest.emits.push(EmitManifest {
+          name: name.to_string(),
+          payload,
+          description: self.description(property.span.start),
+          start: property.span.start,
+          end: property.span.end,
+        });
+      }
+    } else {
+      for (name, span) in string_elements(value) {
+        self.manifest.emits.push(EmitManifest {
+          name: name.to_string(),
+          payload: vec![],
+          description: self.description(span.start),
+          start: span.start,
+          end: span.end,
+        });
+      }
+    }
+  }
+
+  /// `defineModel()`, `defineModel<string>('title', { required: true })`
+  fn define_model(&mut self, call: &CallExpression) {
+    let mut name = "modelValue";
+    let mut r#type = call
+      .type_arguments
+      .as_ref()
+      .and_then(|args| args.params.first())
+      .map(|ty| self.text(ty.span()));
+
+    for argument in &call.arguments {
+      match argument {
+        Argument::StringLiteral(lit) => name = lit.value.as_str(),
+        Argument::ObjectExpression(options) => {
+          for option in &options.properties {
+            let ObjectPropertyKind::ObjectProperty(option) = option else { continue };
+            if key_name(&option.key) == Some("type") && r#type.is_none() {
+              r#type = Some(self.text(option.value.span()));
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+
+    self.manifest.models.push(ModelManifest {
+      name: name.to_string(),
+      r#type,
+      description: self.description(call.span.start),
+      start: call.span.start,
+      end: call.span.end,
+    });
+  }
+
+  /// `defineSlots<{ default(props: { item: Item }): any }>()`
+  fn define_slots<'a>(&mut self, call: &CallExpression<'a>, types: &TypeScope<'_, 'a>) {
+    let Some(members) = type_argument_members(call, types) else { return };
+    for member in members {
+      let (key, params) = match member {
+        TSSignature::TSMethodSignature(signature) => (&signature.key, Some(&*signature.params)),
+        TSSignature::TSPropertySignature(signature) => (
+          &signature.key,
+          match signature.type_annotation.as_ref().map(|ty| &ty.type_annotation) {
+            Some(TSType::TSFunctionType(function)) => Some(&*function.params),
+            _ => None,
+          },
+        ),
+        _ => continue,
+      };
+      let Some(name) = key_name(key) else { continue };
+      let r#type = params
+        .and_then(|params| params.items.first())
+        .and_then(|param| param.type_annotation.as_ref())
+        .map(|ty| self.text(ty.type_annotation.span()));
+
+      self.manifest.slots.push(SlotManifest {
+        name: name.to_string(),
+        props: vec![],
+        r#type,
+        description: self.description(member.span().start),
+        start: member.span().start,
+        end: member.span().end,
+      });
+    }
+  }
+
+  fn set_name(&mut self, value: &Expression) {
+    if let Expression::StringLiteral(lit) = value {
+      self.manifest.name = Some(lit.value.to_string());
+    }
+  }
+
+  fn set_prop_default(&mut self, name: &str, default: String) {
+    if let Some(prop) = self.manifest.props.iter_mut().find(|prop| prop.name == name) {
+      prop.default = Some(default);
+    }
+  }
+
+  fn push_expose(&mut self, name: &str, span: Span) {
+    self.manifest.exposes.push(ExposeManifest {
+      name: name.to_string(),
+      description: self.description(span.start),
+      start: span.start,
+      end: span.end,
+    });
+  }
+
+  fn params(&self, params: &FormalParameters) -> Vec<String> {
+    params.items.iter().map(|param| self.text(param.span)).collect()
+  }
+}
+
+/// Members of the first type argument, following a reference to a local interface or type alias.
+fn type_argument_members<'p, 'a>(
+  call: &'p CallExpression<'a>,
+  types: &TypeScope<'p, 'a>,
+) -> Option<&'p [TSSignature<'a>]> {
+  match call.type_arguments.as_ref()?.params.first()? {
+    TSType::TSTypeLiteral(literal) => Some(&literal.members),
+    TSType::TSTypeReference(reference) => match &reference.type_name {
+      TSTypeName::IdentifierReference(ident) => types.get(ident.name.as_str()).copied(),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+fn collect_types<'p, 'a>(statements: &'p [Statement<'a>], types: &mut TypeScope<'p, 'a>) {
+  for statement in statements {
+    let declaration = match statement {
+      Statement::ExportNamedDeclaration(decl) => decl.declaration.as_ref(),
+      _ => statement.as_declaration(),
+    };
+    match declaration {
+      Some(Declaration::TSInterfaceDeclaration(decl)) => {
+        types.insert(decl.id.name.as_str(), &decl.body.body);
+      }
+      Some(Declaration::TSTypeAliasDeclaration(decl)) => {
+        if let TSType::TSTypeLiteral(literal) = &decl.type_annotation {
+          types.insert(decl.id.name.as_str(), &literal.members);
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+fn string_elements<'a>(value: &Expression<'a>) -> Vec<(&'a str, Span)> {
+  let Expression::ArrayExpression(array) = value else { return vec![] };
+  array
+    .elements
+    .iter()
+    .filter_map(|element| match element {
+      ArrayExpressionElement::StringLiteral(lit) => Some((lit.value.as_str(), lit.span)),
+      _ => None,
+    })
+    .collect()
+}
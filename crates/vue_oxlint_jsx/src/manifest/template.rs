@@ -0,0 +1,81 @@
+use oxc_ast::ast::{
+  Argument, CallExpression, Expression, MemberExpression, ObjectPropertyKind, PropertyKey,
+  Statement,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::GetSpan;
+
+use crate::manifest::{ManifestBuilder, SlotManifest, SlotPropManifest};
+
+impl ManifestBuilder<'_> {
+  /// Collect the `<slot>` outlets of the SFC structure fragment.
+  pub(super) fn analyze_template(&mut self, fragment: &Statement) {
+    let mut collector = SlotCollector { builder: self };
+    collector.visit_statement(fragment);
+  }
+
+  /// A `<slot>` outlet is lowered into `$slots[name]?.(props)`, with the props object spanning
+  /// the opening tag.
+  fn add_slot_outlet(&mut self, member: &MemberExpression, call: &CallExpression) {
+    let Some(Argument::ObjectExpression(object)) = call.arguments.first() else { return };
+    let span = object.span;
+
+    let name = match member {
+      MemberExpression::StaticMemberExpression(member) => member.property.name.to_string(),
+      MemberExpression::ComputedMemberExpression(member) => match &member.expression {
+        Expression::StringLiteral(lit) => lit.value.to_string(),
+        expression => format!("[{}]", self.text(expression.span())),
+      },
+      MemberExpression::PrivateFieldExpression(_) => return,
+    };
+
+    let mut props = vec![];
+    for property in &object.properties {
+      // `v-bind="obj"` spreads are not named props, so they are not listed
+      let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+      let PropertyKey::StaticIdentifier(key) = &property.key else { continue };
+      let expression = match &property.value {
+        Expression::StringLiteral(lit) => {
+          lit.raw.map_or_else(|| format!("{:?}", lit.value.as_str()), |raw| raw.to_string())
+        }
+        // `:item` shorthand binds the same-name variable
+        value if value.span().is_empty() => key.name.to_string(),
+        value => self.text(value.span()),
+      };
+      props.push(SlotPropManifest { name: key.name.to_string(), expression });
+    }
+
+    if let Some(slot) = self.manifest.slots.iter_mut().find(|slot| slot.name == name) {
+      for prop in props {
+        if !slot.props.iter().any(|existing| existing.name == prop.name) {
+          slot.props.push(prop);
+        }
+      }
+    } else {
+      self.manifest.slots.push(SlotManifest {
+        name,
+        props,
+        r#type: None,
+        description: self.description(span.start),
+        start: span.start,
+        end: span.end,
+      });
+    }
+  }
+}
+
+struct SlotCollector<'b, 'm> {
+  builder: &'b mut ManifestBuilder<'m>,
+}
+
+impl<'a> Visit<'a> for SlotCollector<'_, '_> {
+  fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+    if let Some(member) = it.callee.as_member_expression()
+      && let Expression::Identifier(object) = member.object()
+      && object.name == "$slots"
+    {
+      self.builder.add_slot_outlet(member, it);
+    }
+    walk::walk_call_expression(self, it);
+  }
+}
@@ -0,0 +1,376 @@
+//! VitePress Markdown pages, which are Vue SFCs written in Markdown.
+//!
+//! VitePress hoists the `<script>` and `<style>` blocks of a page and compiles the rendered
+//! Markdown as the template. [`MarkdownSfc`] synthesizes the same SFC without rendering any
+//! Markdown: the page becomes the `<template>`, with the hoisted blocks, the front matter, the
+//! code fences and the inline code blanked out, since VitePress escapes them with `v-pre`. The
+//! hoisted blocks follow the template, and every span of the SFC maps back to the page.
+
+use oxc_diagnostics::{LabeledSpan, OxcDiagnostic};
+use oxc_span::Span;
+
+use crate::VueJsxCodegenReturn;
+
+const TEMPLATE_OPEN: &str = "<template>";
+const TEMPLATE_CLOSE: &str = "</template>\n";
+
+/// The SFC of a VitePress Markdown page.
+///
+/// # Examples
+///
+/// ```
+/// use vue_oxlint_jsx::{MarkdownSfc, VueJsxCodegen};
+///
+/// let page = "<script setup>\nconst count = 0\n</script>\n\n# Counter\n\nThe count is {{ count }}.\n";
+/// let sfc = MarkdownSfc::new(page);
+///
+/// let mut ret = VueJsxCodegen::new(&sfc.source_text).build();
+/// sfc.remap_codegen_return(&mut ret);
+/// assert!(ret.source_text.contains("{count}"));
+/// ```
+#[derive(Debug)]
+pub struct MarkdownSfc {
+  /// The synthesized SFC.
+  pub source_text: String,
+  /// The spans of [`Self::source_text`] copied from the page, in order.
+  pub mappings: Vec<MarkdownMapping>,
+}
+
+/// A span of the synthesized SFC and the span of the Markdown page it comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkdownMapping {
+  pub sfc_span: Span,
+  pub markdown_span: Span,
+}
+
+impl MarkdownSfc {
+  /// Synthesize the SFC of a Markdown page.
+  #[must_use]
+  pub fn new(markdown: &str) -> Self {
+    let mut body = markdown.as_bytes().to_vec();
+    let hoisted = blank_blocks(markdown, &mut body);
+    blank_inline(&mut body);
+    // SAFETY: only whole ASCII constructs or single bytes are replaced by ASCII spaces, the
+    // bytes of a multi-byte character are all replaced together
+    let body = unsafe { String::from_utf8_unchecked(body) };
+
+    let mut source_text = String::with_capacity(markdown.len() * 2);
+    let mut mappings = Vec::with_capacity(hoisted.len() + 1);
+    source_text.push_str(TEMPLATE_OPEN);
+    mappings.push(MarkdownMapping {
+      sfc_span: Span::sized(source_text.len() as u32, body.len() as u32),
+      markdown_span: Span::sized(0, body.len() as u32),
+    });
+    source_text.push_str(&body);
+    source_text.push_str(TEMPLATE_CLOSE);
+    for span in hoisted {
+      mappings.push(MarkdownMapping {
+        sfc_span: Span::sized(source_text.len() as u32, span.size()),
+        markdown_span: span,
+      });
+      source_text.push_str(span.source_text(markdown));
+      source_text.push('\n');
+    }
+
+    Self { source_text, mappings }
+  }
+
+  /// The offset of the page an offset of the SFC comes from. The offsets of the synthesized
+  /// `<template>` tags are moved to the start of the following mapped span.
+  #[must_use]
+  pub fn to_markdown_offset(&self, offset: u32) -> u32 {
+    self.mappings.iter().find(|mapping| offset <= mapping.sfc_span.end).map_or_else(
+      || self.mappings.last().map_or(0, |mapping| mapping.markdown_span.end),
+      |mapping| mapping.markdown_span.start + offset.saturating_sub(mapping.sfc_span.start),
+    )
+  }
+
+  /// The span of the page a span of the SFC comes from.
+  #[must_use]
+  pub fn to_markdown_span(&self, span: Span) -> Span {
+    let start = self.to_markdown_offset(span.start);
+    Span::new(start, self.to_markdown_offset(span.end).max(start))
+  }
+
+  /// Move the labels of a diagnostic of the SFC to the page.
+  pub fn remap_diagnostic(&self, mut diagnostic: OxcDiagnostic) -> OxcDiagnostic {
+    if let Some(labels) = &mut diagnostic.labels {
+      for label in labels.iter_mut() {
+        let span = self.to_markdown_span(Span::sized(label.offset() as u32, label.len() as u32));
+        *label = LabeledSpan::new(
+          label.label().map(str::to_string),
+          span.start as usize,
+          span.size() as usize,
+        );
+      }
+    }
+    diagnostic
+  }
+
+  /// Move the spans of the SFC in the result of [`crate::VueJsxCodegen::build`], which was run
+  /// on [`Self::source_text`], to the page.
+  pub fn remap_codegen_return(&self, ret: &mut VueJsxCodegenReturn) {
+    for comment in &mut ret.comments {
+      comment.span = self.to_markdown_span(comment.span);
+      comment.attached_to = self.to_markdown_offset(comment.attached_to);
+    }
+    for span in &mut ret.irregular_whitespaces {
+      *span = self.to_markdown_span(*span);
+    }
+    for mapping in ret
+      .mappings
+      .iter_mut()
+      .chain(ret.virtual_documents.iter_mut().flat_map(|document| &mut document.mappings))
+    {
+      mapping.original_span = self.to_markdown_span(mapping.original_span);
+    }
+    ret.errors = ret.errors.drain(..).map(|error| self.remap_diagnostic(error)).collect();
+  }
+}
+
+/// Blank the front matter, the code fences and the hoisted `<script>` and `<style>` blocks of
+/// the page in `body`, returning the spans of the hoisted blocks.
+fn blank_blocks(markdown: &str, body: &mut [u8]) -> Vec<Span> {
+  let mut hoisted = vec![];
+  let mut lines = Lines { text: markdown, offset: 0 };
+
+  if markdown.starts_with("---")
+    && let Some(first) = lines.next()
+    && first.trim_end() == "---"
+  {
+    let end = lines.find(|line| line.trim_end() == "---").map_or(markdown.len(), |line| {
+      line.as_ptr() as usize - markdown.as_ptr() as usize + line.len()
+    });
+    blank(body, 0, end);
+  }
+
+  while let Some(line) = lines.next() {
+    let start = line.as_ptr() as usize - markdown.as_ptr() as usize;
+    let trimmed = line.trim_start_matches(' ');
+    let indent = line.len() - trimmed.len();
+    if indent > 3 {
+      continue;
+    }
+
+    if let Some(open) = fence(trimmed) {
+      let end = lines
+        .find(|line| {
+          let line = line.trim_start_matches(' ');
+          fence(line).is_some_and(|close| close.starts_with(open))
+            && line.trim_end()[open.len()..].trim_start_matches(&open[..1]).is_empty()
+        })
+        .map_or(markdown.len(), |line| {
+          line.as_ptr() as usize - markdown.as_ptr() as usize + line.len()
+        });
+      blank(body, start, end);
+    } else if let Some(tag) = hoisted_tag(trimmed) {
+      let block_start = start + indent;
+      let close = format!("</{tag}>");
+      let end = memchr::memmem::find(&markdown.as_bytes()[block_start..], close.as_bytes())
+        .map_or(markdown.len(), |index| block_start + index + close.len());
+      blank(body, block_start, end);
+      hoisted.push(Span::new(block_start as u32, end as u32));
+      lines.offset = end;
+    }
+  }
+  hoisted
+}
+
+/// Blank the inline code and autolinks in the text of the template, skipping tags, comments and
+/// interpolations, whose backticks are JavaScript.
+fn blank_inline(body: &mut [u8]) {
+  let mut i = 0;
+  while i < body.len() {
+    match body[i] {
+      b'<' if body[i..].starts_with(b"<!--") => {
+        i = find(body, i + 4, b"-->").map_or(body.len(), |end| end + 3);
+      }
+      b'<' if autolink_len(&body[i..]).is_some() => {
+        let len = autolink_len(&body[i..]).unwrap_or(1);
+        blank(body, i, i + len);
+        i += len;
+      }
+      b'<' if body.get(i + 1).is_some_and(|&byte| byte.is_ascii_alphabetic() || byte == b'/') => {
+        i = tag_end(body, i + 1);
+      }
+      b'{' if body[i..].starts_with(b"{{") => {
+        i = find(body, i + 2, b"}}").map_or(body.len(), |end| end + 2);
+      }
+      b'\\' => i += 2,
+      b'`' => {
+        let run = body[i..].iter().take_while(|&&byte| byte == b'`').count();
+        match closing_backticks(body, i + run, run) {
+          Some(end) => {
+            blank(body, i, end);
+            i = end;
+          }
+          None => i += run,
+        }
+      }
+      _ => i += 1,
+    }
+  }
+}
+
+/// The lines of the page, whose offset is moved past the hoisted blocks.
+struct Lines<'a> {
+  text: &'a str,
+  offset: usize,
+}
+
+impl<'a> Iterator for Lines<'a> {
+  type Item = &'a str;
+
+  /// The next line, without its line break.
+  fn next(&mut self) -> Option<&'a str> {
+    if self.offset >= self.text.len() {
+      return None;
+    }
+    let rest = &self.text[self.offset..];
+    let len = memchr::memchr(b'\n', rest.as_bytes()).unwrap_or(rest.len());
+    self.offset += len + 1;
+    Some(rest[..len].trim_end_matches('\r'))
+  }
+}
+
+/// The opening run of a code fence, like ` ``` ` or `~~~~`.
+fn fence(line: &str) -> Option<&str> {
+  let char = line.bytes().next().filter(|&byte| byte == b'`' || byte == b'~')?;
+  let len = line.bytes().take_while(|&byte| byte == char).count();
+  // The info string of a backtick fence can't contain backticks
+  (len >= 3 && (char == b'~' || !line[len..].contains('`'))).then(|| &line[..len])
+}
+
+/// The tag name of a block VitePress hoists out of the template.
+fn hoisted_tag(line: &str) -> Option<&'static str> {
+  ["script", "style"].into_iter().find(|tag| {
+    line
+      .strip_prefix('<')
+      .and_then(|line| line.strip_prefix(tag))
+      .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '>']))
+  })
+}
+
+/// The length of an autolink like `<https://vuejs.org>`.
+fn autolink_len(text: &[u8]) -> Option<usize> {
+  let scheme = text[1..]
+    .iter()
+    .take_while(|&&byte| byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'.' | b'-'))
+    .count();
+  if !(2..=32).contains(&scheme)
+    || !text[1].is_ascii_alphabetic()
+    || text.get(1 + scheme) != Some(&b':')
+  {
+    return None;
+  }
+  let end =
+    text[1..].iter().position(|&byte| matches!(byte, b'<' | b'>') || byte.is_ascii_whitespace())?;
+  (text[1 + end] == b'>').then_some(end + 2)
+}
+
+/// The end of a tag starting at `i`, after its `>`, skipping quoted attribute values.
+fn tag_end(body: &[u8], mut i: usize) -> usize {
+  while i < body.len() {
+    match body[i] {
+      b'>' => return i + 1,
+      quote @ (b'"' | b'\'') => {
+        i = memchr::memchr(quote, &body[i + 1..]).map_or(body.len(), |end| i + 1 + end + 1);
+      }
+      _ => i += 1,
+    }
+  }
+  body.len()
+}
+
+/// The end of the run of `len` backticks closing an inline code, which must be in the same
+/// paragraph.
+fn closing_backticks(body: &[u8], mut i: usize, len: usize) -> Option<usize> {
+  while i < body.len() {
+    if body[i..].starts_with(b"\n\n") || body[i..].starts_with(b"\n\r\n") {
+      return None;
+    }
+    if body[i] == b'`' {
+      let run = body[i..].iter().take_while(|&&byte| byte == b'`').count();
+      if run == len {
+        return Some(i + run);
+      }
+      i += run;
+    } else {
+      i += 1;
+    }
+  }
+  None
+}
+
+fn find(body: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+  memchr::memmem::find(&body[from..], needle).map(|index| from + index)
+}
+
+/// Replace the bytes of `start..end` but line breaks by spaces.
+fn blank(body: &mut [u8], start: usize, end: usize) {
+  for byte in &mut body[start..end] {
+    if !matches!(*byte, b'\n' | b'\r') {
+      *byte = b' ';
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    VueJsxCodegen,
+    test::{read_file, snapshot_name},
+  };
+
+  use super::MarkdownSfc;
+
+  fn test_markdown(file_path: &str) {
+    let markdown = read_file(file_path);
+    let sfc = MarkdownSfc::new(&markdown);
+    let mut ret = VueJsxCodegen::new(&sfc.source_text).build();
+    sfc.remap_codegen_return(&mut ret);
+
+    let errors: Vec<_> = ret
+      .errors
+      .iter()
+      .flat_map(|error| {
+        error.labels.iter().flatten().map(|label| {
+          let slice = &markdown[label.offset()..label.offset() + label.len()];
+          format!("{}: {slice:?}", error.message)
+        })
+      })
+      .collect();
+    let result = format!(
+      "=============== SFC ===============\n\n{}\n\n=============== Mappings ===============\n\n{:#?}\n\n=============== Codegen ===============\n\n{}\n\n===============  Error  ===============\n\n{errors:#?}",
+      sfc.source_text, sfc.mappings, ret.source_text,
+    );
+
+    let mut settings = insta::Settings::clone_current();
+    settings.set_snapshot_path("test/snapshots/markdown");
+    settings.set_prepend_module_to_snapshot(false);
+    settings.bind(|| {
+      insta::assert_snapshot!(snapshot_name(file_path), result);
+    });
+  }
+
+  #[test]
+  fn page() {
+    test_markdown("markdown/page.md");
+  }
+
+  #[test]
+  fn errors() {
+    test_markdown("markdown/errors.md");
+  }
+
+  #[test]
+  fn maps_offsets_to_the_page() {
+    let markdown = "<script setup>\nconst a = 1\n</script>\n\n{{ a }}\n";
+    let sfc = MarkdownSfc::new(markdown);
+    let sfc_offset = sfc.source_text.find("{{ a }}").unwrap() as u32;
+    assert_eq!(sfc.to_markdown_offset(sfc_offset), markdown.find("{{ a }}").unwrap() as u32);
+    let sfc_offset = sfc.source_text.find("const a").unwrap() as u32;
+    assert_eq!(sfc.to_markdown_offset(sfc_offset), markdown.find("const a").unwrap() as u32);
+    assert_eq!(sfc.to_markdown_offset(0), 0);
+  }
+}
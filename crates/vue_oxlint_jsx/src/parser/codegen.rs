@@ -20,6 +20,26 @@ impl<'a> ParserImpl<'a> {
     result.push_str("__");
     self.ast.str(&result)
   }
+
+  /// Template text may contain `{`, `}`, `<` and `>`, which end a JSX text, so they are printed as
+  /// character references. Other references are kept as written.
+  pub(super) fn codegen_jsx_text(&self, raw: &'a str) -> Str<'a> {
+    if !raw.contains(['{', '}', '<', '>']) {
+      return raw.into();
+    }
+
+    let mut result = String::with_capacity(raw.len());
+    for ch in raw.chars() {
+      match ch {
+        '{' => result.push_str("&#123;"),
+        '}' => result.push_str("&#125;"),
+        '<' => result.push_str("&lt;"),
+        '>' => result.push_str("&gt;"),
+        _ => result.push(ch),
+      }
+    }
+    self.ast.str(&result)
+  }
 }
 
 fn is_codegen_safe_jsx_identifier(name: &str) -> bool {
@@ -0,0 +1,306 @@
+//! CSS Modules of `<style module>` blocks.
+//!
+//! The class names exported by each module are declared as an object binding named after the
+//! module (`$style` by default), so `$style.header` in the template resolves to it. Accesses of
+//! classes which are not exported, and exported classes which are never accessed through the
+//! module name or a `useCssModule()` result, are reported.
+
+use oxc_allocator::Vec as ArenaVec;
+use oxc_ast::ast::{
+  Expression, IdentifierReference, JSXChild, MemberExpression, Statement, VariableDeclarator,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::Span;
+use rustc_hash::FxHashMap;
+use vue_compiler_core::{
+  parser::{ElemProp, Element},
+  util::{find_prop, prop_finder},
+};
+
+use crate::parser::{ParserImpl, error, parse::SourceLocatonSpan};
+
+/// A `<style module>` block.
+pub struct CssModule<'a> {
+  /// `$style`, or the name of `<style module="name">`.
+  pub name: &'a str,
+  /// Exported class names, each spanning its first selector (without the leading `.`).
+  pub classes: Vec<(&'a str, Span)>,
+  /// Whether the classes are all known, which is not the case with selectors built by Sass
+  /// nesting like `&-title`.
+  pub complete: bool,
+}
+
+impl<'a> ParserImpl<'a> {
+  /// Collect the exported classes of a `<style module>` block.
+  pub fn analyze_css_module(&mut self, node: &Element<'a>) {
+    let Some(module) = prop_finder(node, "module").allow_empty().find() else { return };
+    let name = match module.get_ref() {
+      ElemProp::Attr(p) => {
+        p.value.as_ref().map(|value| value.content.raw).filter(|name| !name.is_empty())
+      }
+      ElemProp::Dir(_) => return,
+    }
+    .unwrap_or("$style");
+    let lang = find_prop(node, "lang").and_then(|p| match p.get_ref() {
+      ElemProp::Attr(p) => p.value.as_ref().map(|value| value.content.raw),
+      ElemProp::Dir(_) => None,
+    });
+    let line_comments = matches!(lang, Some("scss" | "sass" | "less" | "stylus" | "styl"));
+
+    // The classes of `<style module src>` are in another file
+    let mut module = CssModule { name, classes: vec![], complete: Self::block_src(node).is_none() };
+    if let (Some(first), Some(last)) = (node.children.first(), node.children.last()) {
+      let start = first.get_location().span().start;
+      let css = Span::new(start, last.get_location().span().end).source_text(self.source_text);
+      let selectors = css_module_classes(css, line_comments);
+      module.complete &= selectors.complete;
+      for (class_start, class_end) in selectors.classes {
+        let class = &css[class_start as usize..class_end as usize];
+        if module.classes.iter().all(|(name, _)| *name != class) {
+          module.classes.push((class, Span::new(start + class_start, start + class_end)));
+        }
+      }
+    }
+    self.css_modules.push(module);
+  }
+
+  /// Report unknown and unused CSS module classes among the script statements and the SFC
+  /// `children`, then declare each module as an object of its classes in `<script setup>`.
+  pub fn finish_css_modules(&mut self, children: &ArenaVec<'a, JSXChild<'a>>) {
+    if self.css_modules.is_empty() {
+      return;
+    }
+
+    let mut usages = CssModuleUsages::default();
+    for module in &self.css_modules {
+      usages.aliases.insert(module.name, module.name);
+    }
+    for statement in self.global.statements.iter().chain(&self.setup.statements) {
+      usages.collect_aliases(statement);
+    }
+    for statement in self.global.statements.iter().chain(&self.setup.statements) {
+      usages.visit_statement(statement);
+    }
+    for child in children {
+      usages.visit_jsx_child(child);
+    }
+
+    for module in &self.css_modules {
+      let accesses = usages.accesses.iter().filter(|access| access.module == module.name);
+      for access in accesses {
+        if module.complete && module.classes.iter().all(|(name, _)| *name != access.class) {
+          error::unknown_css_module_class(&mut self.errors, module.name, access.class, access.span);
+        }
+      }
+      if usages.dynamic.contains(&module.name) {
+        continue;
+      }
+      for (class, span) in &module.classes {
+        if !usages
+          .accesses
+          .iter()
+          .any(|access| access.module == module.name && access.class == *class)
+        {
+          error::unused_css_module_class(&mut self.errors, module.name, class, *span);
+        }
+      }
+    }
+
+    for module in std::mem::take(&mut self.css_modules) {
+      // Respect a user declaration like `const $style = useCssModule()`
+      if !self.script_bindings.contains(module.name) {
+        self.declare_key_object(module.name, &module.classes);
+      }
+    }
+  }
+}
+
+/// A static class access like `$style.header` or `classes['title-main']`.
+struct CssModuleAccess<'a> {
+  module: &'a str,
+  class: &'a str,
+  span: Span,
+}
+
+#[derive(Default)]
+struct CssModuleUsages<'a> {
+  /// Local names referring to a module: the module names themselves, and the variables assigned
+  /// from `useCssModule()` / `useCssModule('name')`.
+  aliases: FxHashMap<&'a str, &'a str>,
+  accesses: Vec<CssModuleAccess<'a>>,
+  /// Modules used as a whole (e.g. `:class="$style"` or `$style[name]`), whose used classes
+  /// can't be known.
+  dynamic: Vec<&'a str>,
+}
+
+impl<'a> CssModuleUsages<'a> {
+  /// Record `const classes = useCssModule('name')` at the top level.
+  fn collect_aliases(&mut self, statement: &Statement<'a>) {
+    let Statement::VariableDeclaration(declaration) = statement else { return };
+    for VariableDeclarator { id, init, .. } in &declaration.declarations {
+      let (Some(id), Some(Expression::CallExpression(call))) =
+        (id.get_binding_identifier(), init.as_ref().map(Expression::get_inner_expression))
+      else {
+        continue;
+      };
+      if !call.callee.is_specific_id("useCssModule") {
+        continue;
+      }
+      let module = match call.arguments.first().and_then(|argument| argument.as_expression()) {
+        Some(Expression::StringLiteral(name)) => name.value.as_str(),
+        None => "$style",
+        Some(_) => continue,
+      };
+      self.aliases.insert(id.name.as_str(), module);
+    }
+  }
+
+  /// The module a `$style` or `this.$style` object refers to.
+  fn module_of(&self, object: &Expression<'a>) -> Option<&'a str> {
+    match object.get_inner_expression() {
+      Expression::Identifier(ident) => self.aliases.get(ident.name.as_str()).copied(),
+      Expression::StaticMemberExpression(member)
+        if matches!(member.object, Expression::ThisExpression(_))
+          && member.property.name.starts_with('$') =>
+      {
+        self.aliases.get(member.property.name.as_str()).copied()
+      }
+      _ => None,
+    }
+  }
+}
+
+impl<'a> Visit<'a> for CssModuleUsages<'a> {
+  fn visit_member_expression(&mut self, it: &MemberExpression<'a>) {
+    if let Some(module) = self.module_of(it.object())
+      && let Some((span, class)) = it.static_property_info()
+    {
+      self.accesses.push(CssModuleAccess { module, class, span });
+      // The object is accounted for, so don't visit it as a whole
+      if let MemberExpression::ComputedMemberExpression(member) = it {
+        self.visit_expression(&member.expression);
+      }
+      return;
+    }
+    walk::walk_member_expression(self, it);
+  }
+
+  fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+    if let Some(&module) = self.aliases.get(it.name.as_str()) {
+      self.dynamic.push(module);
+    }
+  }
+}
+
+/// The classes found by [`css_module_classes`].
+struct CssModuleClasses {
+  /// Byte ranges of the class names, without the leading `.`.
+  classes: Vec<(u32, u32)>,
+  complete: bool,
+}
+
+/// Collect the class selectors of a CSS Modules stylesheet with a minimal tokenizer.
+///
+/// Only the preludes of rules (the text before `{`) are scanned, skipping at-rules (`@media`),
+/// comments, strings, attribute selectors and `:global(...)` / `:global .foo` selectors, which
+/// are not exported.
+fn css_module_classes(css: &str, line_comments: bool) -> CssModuleClasses {
+  let bytes = css.as_bytes();
+  let mut result = CssModuleClasses { classes: vec![], complete: true };
+  let mut prelude_start = 0;
+  let mut i = 0;
+
+  while i < bytes.len() {
+    match bytes[i] {
+      b'/' if bytes.get(i + 1) == Some(&b'*') => {
+        i = css[i..].find("*/").map_or(bytes.len(), |end| i + end + 2);
+        continue;
+      }
+      b'/' if line_comments && bytes.get(i + 1) == Some(&b'/') => {
+        i = css[i..].find('\n').map_or(bytes.len(), |end| i + end);
+        continue;
+      }
+      quote @ (b'"' | b'\'') => {
+        i = css[i + 1..].find(quote as char).map_or(bytes.len(), |end| i + 1 + end);
+      }
+      b'{' => {
+        scan_selector(css, prelude_start, i, line_comments, &mut result);
+        prelude_start = i + 1;
+      }
+      b';' | b'}' => prelude_start = i + 1,
+      _ => {}
+    }
+    i += 1;
+  }
+
+  result
+}
+
+/// Collect the classes of the selector `css[start..end]`.
+fn scan_selector(
+  css: &str,
+  start: usize,
+  end: usize,
+  line_comments: bool,
+  result: &mut CssModuleClasses,
+) {
+  let bytes = css.as_bytes();
+  let selector = css[start..end].trim_start();
+  if selector.starts_with('@') || selector.starts_with(":export") {
+    return;
+  }
+
+  let mut i = start;
+  let mut global = false;
+  while i < end {
+    let rest = &css[i..end];
+    match bytes[i] {
+      b'/' if rest.starts_with("/*") => {
+        i = rest.find("*/").map_or(end, |close| i + close + 2);
+        continue;
+      }
+      b'/' if line_comments && rest.starts_with("//") => {
+        i = rest.find('\n').map_or(end, |close| i + close);
+      }
+      b'[' => {
+        i = rest.find(']').map_or(end, |close| i + close);
+      }
+      b',' => global = false,
+      b':' if rest.starts_with(":global(") => {
+        i = rest.find(')').map_or(end, |close| i + close);
+      }
+      b':' if rest.starts_with(":global") => global = true,
+      b'&' if bytes.get(i + 1).is_some_and(|&byte| is_class_char(byte)) => result.complete = false,
+      b'.' if !global && bytes.get(i + 1).is_some_and(|&byte| is_class_start(byte)) => {
+        let class_end =
+          rest[1..].bytes().position(|byte| !is_class_char(byte)).map_or(end, |len| i + 1 + len);
+        result.classes.push((i as u32 + 1, class_end as u32));
+        i = class_end;
+        continue;
+      }
+      _ => {
+        // Keep `i` on a char boundary, as `rest` is sliced at it
+        i += rest.chars().next().map_or(1, char::len_utf8);
+        continue;
+      }
+    }
+    i += 1;
+  }
+}
+
+const fn is_class_start(byte: u8) -> bool {
+  byte.is_ascii_alphabetic() || matches!(byte, b'_' | b'-') || !byte.is_ascii()
+}
+
+const fn is_class_char(byte: u8) -> bool {
+  is_class_start(byte) || byte.is_ascii_digit()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(css_modules_vue, "style/css-modules.vue", true, false);
+  test_ast!(css_modules_declaration_vue, "style/css-modules-declaration.vue");
+  test_ast!(css_modules_non_ascii_vue, "style/css-modules-non-ascii.vue", true, false);
+}
@@ -0,0 +1,76 @@
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Expression;
+use oxc_span::Span;
+
+use super::{
+  CustomBlock, CustomBlockHandler, CustomBlockReturn, SyntheticDeclaration,
+  json::{object_members, parse_json},
+  yaml::check_yaml,
+};
+
+/// The name of the object declaring the message keys of `<i18n>` blocks, which is also where
+/// `@intlify/unplugin-vue-i18n` attaches the messages (`Component.__i18n`).
+const DECLARATION_NAME: &str = "__i18n";
+
+/// Validates `<i18n>` blocks of `vue-i18n`, JSON by default, JSON5 with `lang="json5"` and YAML
+/// with `lang="yaml"`.
+///
+/// The keys of JSON and JSON5 messages are declared as `const __i18n = { "a.b": "a.b", ... }`
+/// in `<script setup>`, spanning the message definitions, so the keys of `$t('a.b')` can be
+/// checked against it. Nested objects are flattened into dotted keys, and the top-level keys are
+/// locales unless the block has a `locale` attribute.
+pub struct I18nBlockHandler;
+
+impl CustomBlockHandler for I18nBlockHandler {
+  fn handle(&self, block: &CustomBlock<'_>) -> CustomBlockReturn {
+    let mut ret = CustomBlockReturn::default();
+    let json5 = match block.lang() {
+      None | Some("json") => false,
+      Some("json5") => true,
+      Some("yaml" | "yml") => {
+        check_yaml(block.content, block.content_span.start, &mut ret.diagnostics);
+        return ret;
+      }
+      Some(_) => return ret,
+    };
+    if block.content.trim().is_empty() {
+      return ret;
+    }
+
+    let allocator = Allocator::default();
+    let Some(value) =
+      parse_json(&allocator, block.content, block.content_span.start, json5, &mut ret.diagnostics)
+    else {
+      return ret;
+    };
+    let mut keys = vec![];
+    if block.attr("locale").is_some() {
+      collect_keys(&value, "", block.content_span.start, &mut keys);
+    } else if let Some(locales) = object_members(&value) {
+      for (_, _, messages) in locales {
+        collect_keys(messages, "", block.content_span.start, &mut keys);
+      }
+    }
+    ret.declarations.push(SyntheticDeclaration { name: DECLARATION_NAME.to_string(), keys });
+    ret
+  }
+}
+
+/// Collect the dotted keys of the messages (the non-object values) of `object`, whose spans are
+/// moved by `offset` into the SFC.
+fn collect_keys(
+  object: &Expression<'_>,
+  prefix: &str,
+  offset: u32,
+  keys: &mut Vec<(String, Span)>,
+) {
+  let Some(members) = object_members(object) else { return };
+  for (key, span, value) in members {
+    let key = format!("{prefix}{key}");
+    if matches!(value, Expression::ObjectExpression(_)) {
+      collect_keys(value, &format!("{key}."), offset, keys);
+    } else {
+      keys.push((key, span.move_right(offset)));
+    }
+  }
+}
@@ -0,0 +1,134 @@
+//! JSON and JSON5 of custom blocks.
+//!
+//! JSON is validated by `serde_json`. JSON5 is a subset of JS expressions, so both are read by
+//! oxc for the spans of the object keys, and JSON5 is validated against the node kinds it allows.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+  ArrayExpressionElement, Expression, ObjectPropertyKind, PropertyKey, PropertyKind, UnaryOperator,
+};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::{GetSpan, SourceType, Span};
+use serde::de::IgnoredAny;
+
+use super::sentence;
+use crate::parser::error;
+
+/// Parse `source`, which starts at `offset` of the SFC, reporting the first syntax error.
+///
+/// `json5` allows comments, trailing commas, single quoted strings, identifier keys and the
+/// JSON5 numbers (`+1`, `.5`, `0x1F`, `Infinity`, `NaN`).
+pub fn parse_json<'a>(
+  allocator: &'a Allocator,
+  source: &'a str,
+  offset: u32,
+  json5: bool,
+  errors: &mut Vec<OxcDiagnostic>,
+) -> Option<Expression<'a>> {
+  let lang = if json5 { "JSON5" } else { "JSON" };
+  if !json5 && let Err(err) = serde_json::from_str::<IgnoredAny>(source) {
+    // serde_json reports 1-based lines and columns, with the columns counted in bytes
+    let line_start: usize =
+      source.split_inclusive('\n').take(err.line().saturating_sub(1)).map(str::len).sum();
+    let start = (line_start + err.column().saturating_sub(1)).min(source.len()) as u32;
+    let message = err.to_string();
+    let message = message.split_once(" at line ").map_or(message.as_str(), |(message, _)| message);
+    error::invalid_json(errors, lang, &sentence(message), Span::empty(offset + start));
+    return None;
+  }
+
+  let expression =
+    match oxc_parser::Parser::new(allocator, source, SourceType::default()).parse_expression() {
+      Ok(expression) => expression,
+      Err(diagnostics) => {
+        let diagnostic = diagnostics.into_iter().next()?;
+        let span = diagnostic.labels.as_ref().and_then(|labels| labels.first()).map_or_else(
+          || Span::empty(0),
+          |label| Span::sized(label.offset() as u32, label.len() as u32),
+        );
+        error::invalid_json(errors, lang, &sentence(&diagnostic.message), span.move_right(offset));
+        return None;
+      }
+    };
+  if json5 && let Err(span) = check_json5(&expression) {
+    let message = format!("Unexpected `{}`.", span.source_text(source));
+    error::invalid_json(errors, lang, &message, span.move_right(offset));
+    return None;
+  }
+  Some(expression)
+}
+
+/// The members of a JSON object, with their keys and the spans of the keys.
+pub fn object_members<'e, 'a>(
+  value: &'e Expression<'a>,
+) -> Option<impl Iterator<Item = (&'a str, Span, &'e Expression<'a>)>> {
+  let Expression::ObjectExpression(object) = value else { return None };
+  Some(object.properties.iter().filter_map(|property| {
+    let ObjectPropertyKind::ObjectProperty(property) = property else { return None };
+    let key = match &property.key {
+      PropertyKey::StaticIdentifier(key) => key.name.as_str(),
+      PropertyKey::StringLiteral(key) => key.value.as_str(),
+      _ => return None,
+    };
+    Some((key, property.key.span(), &property.value))
+  }))
+}
+
+/// Find the first node JSON5 doesn't allow, like a call or a template literal.
+fn check_json5(expression: &Expression) -> Result<(), Span> {
+  match expression {
+    Expression::ObjectExpression(object) => {
+      object.properties.iter().try_for_each(|property| match property {
+        ObjectPropertyKind::ObjectProperty(property)
+          if property.kind == PropertyKind::Init
+            && !property.computed
+            && !property.method
+            && !property.shorthand
+            && matches!(
+              property.key,
+              PropertyKey::StaticIdentifier(_) | PropertyKey::StringLiteral(_)
+            ) =>
+        {
+          check_json5(&property.value)
+        }
+        _ => Err(property.span()),
+      })
+    }
+    Expression::ArrayExpression(array) => {
+      array.elements.iter().try_for_each(|element| match element {
+        ArrayExpressionElement::SpreadElement(_) | ArrayExpressionElement::Elision(_) => {
+          Err(element.span())
+        }
+        _ => check_json5(element.to_expression()),
+      })
+    }
+    Expression::UnaryExpression(unary)
+      if matches!(unary.operator, UnaryOperator::UnaryPlus | UnaryOperator::UnaryNegation)
+        && is_json5_number(&unary.argument) =>
+    {
+      Ok(())
+    }
+    Expression::StringLiteral(_) | Expression::BooleanLiteral(_) | Expression::NullLiteral(_) => {
+      Ok(())
+    }
+    _ if is_json5_number(expression) => Ok(()),
+    _ => Err(expression.span()),
+  }
+}
+
+/// Decimal and hexadecimal numbers, `Infinity` and `NaN`, but not the binary, octal and separated
+/// numbers of JS.
+fn is_json5_number(expression: &Expression) -> bool {
+  match expression {
+    Expression::NumericLiteral(number) => number.raw.is_some_and(|raw| {
+      let raw = raw.as_str();
+      let legacy_octal =
+        raw.len() > 1 && raw.starts_with('0') && raw.as_bytes()[1].is_ascii_digit();
+      !legacy_octal
+        && !raw.contains('_')
+        && !["0b", "0B", "0o", "0O"].iter().any(|prefix| raw.starts_with(prefix))
+    }),
+    Expression::Identifier(identifier) => matches!(identifier.name.as_str(), "Infinity" | "NaN"),
+    _ => false,
+  }
+}
@@ -0,0 +1,264 @@
+//! Custom blocks like `<i18n>`, `<route>` or `<docs>`, which are checked by the
+//! [`CustomBlockHandler`] registered for their tag and `lang`.
+
+use std::{fmt, sync::Arc};
+
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+use vue_compiler_core::parser::{ElemProp, Element};
+
+use crate::parser::{
+  ParserImpl,
+  parse::{SourceLocatonSpan, block_content_span},
+};
+
+mod i18n;
+mod json;
+mod yaml;
+
+pub use i18n::I18nBlockHandler;
+pub use yaml::YamlBlockHandler;
+
+/// Checks the content of a custom block.
+///
+/// # Examples
+///
+/// ```
+/// use oxc_allocator::Allocator;
+/// use oxc_diagnostics::OxcDiagnostic;
+/// use vue_oxlint_jsx::{
+///   CustomBlock, CustomBlockHandler, CustomBlockHandlers, CustomBlockReturn, VueJsxParser,
+/// };
+///
+/// struct NonEmpty;
+///
+/// impl CustomBlockHandler for NonEmpty {
+///   fn handle(&self, block: &CustomBlock<'_>) -> CustomBlockReturn {
+///     let mut ret = CustomBlockReturn::default();
+///     if block.content.trim().is_empty() {
+///       ret.diagnostics.push(OxcDiagnostic::error("Empty <docs> block.").with_label(block.span));
+///     }
+///     ret
+///   }
+/// }
+///
+/// let allocator = Allocator::default();
+/// let ret = VueJsxParser::new(&allocator, "<docs></docs>")
+///   .with_custom_block_handlers(CustomBlockHandlers::new().with_handler("docs", None, NonEmpty))
+///   .parse();
+/// assert_eq!(ret.errors.len(), 1);
+/// ```
+pub trait CustomBlockHandler: Send + Sync {
+  fn handle(&self, block: &CustomBlock<'_>) -> CustomBlockReturn;
+}
+
+/// A custom block passed to [`CustomBlockHandler::handle`]. All spans are offsets in the SFC.
+#[derive(Debug)]
+pub struct CustomBlock<'s> {
+  /// The tag name, e.g. `i18n`.
+  pub tag: &'s str,
+  pub attrs: Vec<CustomBlockAttribute<'s>>,
+  /// Raw text between the start and end tags.
+  pub content: &'s str,
+  /// Span of [`Self::content`].
+  pub content_span: Span,
+  /// Span of the whole block.
+  pub span: Span,
+}
+
+/// An attribute of a custom block's start tag.
+#[derive(Debug)]
+pub struct CustomBlockAttribute<'s> {
+  pub name: &'s str,
+  /// [`None`] for boolean attributes.
+  pub value: Option<&'s str>,
+  pub span: Span,
+}
+
+impl<'s> CustomBlock<'s> {
+  #[must_use]
+  pub fn attr(&self, name: &str) -> Option<&CustomBlockAttribute<'s>> {
+    self.attrs.iter().find(|attr| attr.name == name)
+  }
+
+  /// The `lang` attribute, like `json` or `yaml`.
+  #[must_use]
+  pub fn lang(&self) -> Option<&'s str> {
+    self.attr("lang").and_then(|attr| attr.value)
+  }
+}
+
+/// The result of [`CustomBlockHandler::handle`].
+#[derive(Debug, Default)]
+pub struct CustomBlockReturn {
+  pub diagnostics: Vec<OxcDiagnostic>,
+  /// Objects declared in `<script setup>`. Declarations of the same name from several blocks are
+  /// merged.
+  pub declarations: Vec<SyntheticDeclaration>,
+}
+
+/// A `const name = { "key": "key", ... }` declaration in `<script setup>`, whose keys span their
+/// definitions in the block.
+#[derive(Debug)]
+pub struct SyntheticDeclaration {
+  pub name: String,
+  pub keys: Vec<(String, Span)>,
+}
+
+/// The [`CustomBlockHandler`]s, by tag name and `lang`.
+///
+/// None are registered by default; [`CustomBlockHandlers::builtin`] has the handlers shipped with
+/// this crate.
+#[derive(Clone, Default)]
+pub struct CustomBlockHandlers {
+  handlers: Vec<(String, Option<String>, Arc<dyn CustomBlockHandler>)>,
+}
+
+impl CustomBlockHandlers {
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { handlers: Vec::new() }
+  }
+
+  /// [`I18nBlockHandler`] for `<i18n>`, and [`YamlBlockHandler`] for `<route lang="yaml">`.
+  #[must_use]
+  pub fn builtin() -> Self {
+    Self::new().with_handler("i18n", None, I18nBlockHandler).with_handler(
+      "route",
+      Some("yaml"),
+      YamlBlockHandler,
+    )
+  }
+
+  /// Handle `<tag>` blocks with `handler`, only those whose `lang` attribute is `lang` if it is
+  /// not [`None`]. The handler registered first wins.
+  #[must_use]
+  pub fn with_handler(
+    mut self,
+    tag: impl Into<String>,
+    lang: Option<&str>,
+    handler: impl CustomBlockHandler + 'static,
+  ) -> Self {
+    self.handlers.push((tag.into(), lang.map(str::to_string), Arc::new(handler)));
+    self
+  }
+
+  fn find(&self, tag: &str, lang: Option<&str>) -> Option<&dyn CustomBlockHandler> {
+    self
+      .handlers
+      .iter()
+      .find(|(handler_tag, handler_lang, _)| {
+        handler_tag == tag
+          && handler_lang.as_deref().is_none_or(|handler_lang| Some(handler_lang) == lang)
+      })
+      .map(|(_, _, handler)| handler.as_ref())
+  }
+}
+
+impl fmt::Debug for CustomBlockHandlers {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(self.handlers.iter().map(|(tag, lang, _)| (tag, lang))).finish()
+  }
+}
+
+impl<'a> ParserImpl<'a> {
+  /// Run the handler registered for a custom block, if any.
+  pub fn handle_custom_block(&mut self, node: &Element<'a>) {
+    let attrs: Vec<_> = node
+      .properties
+      .iter()
+      .filter_map(|prop| match prop {
+        ElemProp::Attr(attr) => Some(CustomBlockAttribute {
+          name: attr.name,
+          value: attr.value.as_ref().map(|value| value.content.raw),
+          span: attr.location.span(),
+        }),
+        ElemProp::Dir(_) => None,
+      })
+      .collect();
+    let lang = attrs.iter().find(|attr| attr.name == "lang").and_then(|attr| attr.value);
+    let Some(handler) = self.config.custom_block_handlers.find(node.tag_name, lang) else {
+      return;
+    };
+
+    let content_span = block_content_span(node, self.source_text);
+    let block = CustomBlock {
+      tag: node.tag_name,
+      attrs,
+      content: content_span.source_text(self.source_text),
+      content_span,
+      span: node.location.span(),
+    };
+    let ret = handler.handle(&block);
+
+    self.errors.extend(ret.diagnostics);
+    let ast = self.ast;
+    for declaration in ret.declarations {
+      let index = self
+        .custom_block_declarations
+        .iter()
+        .position(|(name, _)| *name == declaration.name)
+        .unwrap_or_else(|| {
+          self.custom_block_declarations.push((ast.str(&declaration.name).as_str(), vec![]));
+          self.custom_block_declarations.len() - 1
+        });
+      let keys = &mut self.custom_block_declarations[index].1;
+      for (key, span) in declaration.keys {
+        if keys.iter().all(|(name, _)| *name != key) {
+          keys.push((ast.str(&key).as_str(), span));
+        }
+      }
+    }
+  }
+
+  /// Declare the objects contributed by custom block handlers in `<script setup>`.
+  pub fn finish_custom_blocks(&mut self) {
+    for (name, keys) in std::mem::take(&mut self.custom_block_declarations) {
+      if !self.script_bindings.contains(name) {
+        self.declare_key_object(name, &keys);
+      }
+    }
+  }
+}
+
+/// A message of a parser library as a sentence, like `Trailing comma.` for `trailing comma`.
+fn sentence(message: &str) -> String {
+  let mut sentence = message.trim_end_matches('.').to_string();
+  if let Some(first) = sentence.get_mut(..1) {
+    first.make_ascii_uppercase();
+  }
+  sentence.push('.');
+  sentence
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(
+    custom_blocks_vue,
+    "custom_blocks/valid.vue",
+    config: crate::parser::ParseConfig {
+      custom_block_handlers: crate::CustomBlockHandlers::builtin(),
+      ..Default::default()
+    }
+  );
+  test_ast!(
+    custom_blocks_non_ascii_vue,
+    "custom_blocks/non_ascii.vue",
+    config: crate::parser::ParseConfig {
+      custom_block_handlers: crate::CustomBlockHandlers::builtin(),
+      ..Default::default()
+    }
+  );
+  test_ast!(
+    custom_blocks_invalid_vue,
+    "custom_blocks/invalid.vue",
+    true,
+    false,
+    config: crate::parser::ParseConfig {
+      custom_block_handlers: crate::CustomBlockHandlers::builtin(),
+      ..Default::default()
+    }
+  );
+}
@@ -0,0 +1,31 @@
+//! YAML of custom blocks, checked by `yaml-rust2`.
+
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+use yaml_rust2::YamlLoader;
+
+use super::{CustomBlock, CustomBlockHandler, CustomBlockReturn, sentence};
+use crate::parser::error;
+
+/// Checks the syntax of YAML custom blocks, like `<route lang="yaml">` of
+/// `unplugin-vue-router`.
+pub struct YamlBlockHandler;
+
+impl CustomBlockHandler for YamlBlockHandler {
+  fn handle(&self, block: &CustomBlock<'_>) -> CustomBlockReturn {
+    let mut ret = CustomBlockReturn::default();
+    check_yaml(block.content, block.content_span.start, &mut ret.diagnostics);
+    ret
+  }
+}
+
+/// Report the first syntax error of `source`, which starts at `offset` of the SFC, duplicate keys
+/// included.
+pub fn check_yaml(source: &str, offset: u32, errors: &mut Vec<OxcDiagnostic>) {
+  let Err(err) = YamlLoader::load_from_str(source) else { return };
+  // The index of the marker is counted in chars
+  let start = source.char_indices().nth(err.marker().index()).map_or(source.len(), |(i, _)| i);
+  let len = source[start..].chars().next().map_or(0, char::len_utf8);
+  let span = Span::sized(start as u32, len as u32).move_right(offset);
+  error::invalid_yaml(errors, &sentence(err.info()), span);
+}
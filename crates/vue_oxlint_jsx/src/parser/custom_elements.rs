@@ -0,0 +1,68 @@
+use regex::Regex;
+
+/// Tags which are custom elements (web components) rather than Vue components, like Vue's
+/// `compilerOptions.isCustomElement`.
+///
+/// Matching tags stay intrinsic `JSXIdentifier`s with their original name, so they are not
+/// resolved as (undefined) component references.
+///
+/// # Examples
+///
+/// ```
+/// use regex::Regex;
+/// use vue_oxlint_jsx::CustomElements;
+///
+/// let custom_elements =
+///   CustomElements::new().with_prefix("sl-").with_pattern(regex::Regex::new("^ion-[a-z-]+$").unwrap());
+/// assert!(custom_elements.is_custom_element("sl-button"));
+/// assert!(custom_elements.is_custom_element("ion-content"));
+/// assert!(!custom_elements.is_custom_element("my-button"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CustomElements {
+  prefixes: Vec<String>,
+  patterns: Vec<Regex>,
+}
+
+impl CustomElements {
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { prefixes: Vec::new(), patterns: Vec::new() }
+  }
+
+  /// Treat tags starting with `prefix` (e.g. `sl-`) as custom elements.
+  #[must_use]
+  pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+    self.prefixes.push(prefix.into());
+    self
+  }
+
+  /// Treat tags matching `pattern` as custom elements.
+  #[must_use]
+  pub fn with_pattern(mut self, pattern: Regex) -> Self {
+    self.patterns.push(pattern);
+    self
+  }
+
+  #[must_use]
+  pub fn is_custom_element(&self, tag_name: &str) -> bool {
+    self.prefixes.iter().any(|prefix| tag_name.starts_with(prefix.as_str()))
+      || self.patterns.iter().any(|pattern| pattern.is_match(tag_name))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(
+    custom_elements_vue,
+    "custom_elements.vue",
+    config: crate::parser::ParseConfig {
+      custom_elements: crate::CustomElements::new()
+        .with_prefix("sl-")
+        .with_pattern(regex::Regex::new("^ion-[a-z-]+$").unwrap()),
+      ..Default::default()
+    }
+  );
+}
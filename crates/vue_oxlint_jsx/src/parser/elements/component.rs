@@ -0,0 +1,165 @@
+use oxc_allocator::Vec as ArenaVec;
+use oxc_ast::ast::{
+  Expression, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXElementName,
+  JSXMemberExpressionObject,
+};
+use oxc_span::{GetSpan, Span};
+
+use crate::parser::{ParserImpl, elements::kebab_to_case};
+
+/// Components provided by Vue itself, with their kebab-case form.
+///
+/// They are emitted as `JSXIdentifier` rather than references, so they never report as undefined.
+const BUILTIN_COMPONENTS: &[(&str, &str)] = &[
+  ("Transition", "transition"),
+  ("TransitionGroup", "transition-group"),
+  ("KeepAlive", "keep-alive"),
+  ("Teleport", "teleport"),
+  ("Suspense", "suspense"),
+  ("Component", "component"),
+  ("Slot", "slot"),
+];
+
+impl<'a> ParserImpl<'a> {
+  /// The name of a built-in component, unless a `<script>` binding shadows it (e.g. an explicit
+  /// `import { Transition } from 'vue'`).
+  ///
+  /// Codegen mode uses the kebab-case form, as a capitalized name re-parses as a reference.
+  pub fn builtin_component_name(&self, tag_name: &str) -> Option<&'static str> {
+    let (pascal, kebab) = BUILTIN_COMPONENTS
+      .iter()
+      .find(|(pascal, kebab)| tag_name == *pascal || tag_name == *kebab)?;
+    if self.script_bindings.contains(pascal) {
+      return None;
+    }
+    Some(if self.config.codegen { kebab } else { pascal })
+  }
+
+  /// The local identifier of a component registered via the Options API `components` option, e.g.
+  /// `Btn` for `<my-button>` with `components: { MyButton: Btn }`.
+  pub fn local_component_name(&self, span: Span, tag_name: &str) -> Option<JSXElementName<'a>> {
+    let local = self
+      .options_api
+      .as_ref()
+      .and_then(|component| component.component(tag_name))
+      .and_then(|component| component.local)
+      .filter(|local| self.script_bindings.contains(local))
+      // A lowercase name would re-parse as an intrinsic element
+      .filter(|local| {
+        !self.config.codegen || !local.starts_with(|c: char| c.is_ascii_lowercase())
+      })?;
+    Some(self.ast.jsx_element_name_identifier_reference(span, local))
+  }
+
+  /// Resolve the component rendered by `<component :is="Comp">`, `<component is="Comp">` or
+  /// `<tr is="vue:my-row">`.
+  ///
+  /// A bound `:is` becomes the element name, so its value is replaced with an empty placeholder.
+  /// Expressions that can't be an element name (e.g. `cond ? A : B`) are left untouched.
+  pub fn resolve_dynamic_component(
+    &self,
+    tag_name: &str,
+    attributes: &mut ArenaVec<'a, JSXAttributeItem<'a>>,
+  ) -> Option<JSXElementName<'a>> {
+    let is_component = matches!(tag_name, "component" | "Component");
+
+    for attribute in attributes.iter_mut() {
+      let JSXAttributeItem::Attribute(attribute) = attribute else { continue };
+      match &attribute.name {
+        JSXAttributeName::Identifier(ident) if ident.name == "is" => {
+          let Some(JSXAttributeValue::StringLiteral(lit)) = &attribute.value else { continue };
+          return match lit.value.strip_prefix("vue:") {
+            Some(name) => {
+              let span = Span::new(lit.span.start + "vue:".len() as u32, lit.span.end);
+              Some(self.component_reference(span, name))
+            }
+            None if is_component => Some(self.component_reference(lit.span, &lit.value)),
+            // On native elements, a plain `is` is a customized built-in element
+            None => None,
+          };
+        }
+        JSXAttributeName::NamespacedName(name)
+          if is_component
+            && name.namespace.name == "v-bind"
+            && name.name.span.source_text(self.source_text) == "is" =>
+        {
+          let Some(JSXAttributeValue::ExpressionContainer(container)) = &mut attribute.value else {
+            continue;
+          };
+          let expression = container.expression.as_expression()?;
+          let element_name = self.expression_element_name(expression)?;
+          container.expression = self.empty_jsx_attribute_expression();
+          return Some(element_name);
+        }
+        _ => {}
+      }
+    }
+
+    None
+  }
+
+  /// A string `is` value names a registered component, in kebab-case or `PascalCase`. Lowercase
+  /// names without a dash are native elements.
+  fn component_reference(&self, span: Span, name: &str) -> JSXElementName<'a> {
+    let ast = self.ast;
+    if let Some(builtin) = self.builtin_component_name(name) {
+      return ast.jsx_element_name_identifier(span, builtin);
+    }
+    if let Some(local) = self.local_component_name(span, name) {
+      return local;
+    }
+    if name.contains('-') || name.starts_with(|c: char| c.is_ascii_uppercase()) {
+      ast.jsx_element_name_identifier_reference(span, ast.str(&kebab_to_case(name, true)))
+    } else {
+      ast.jsx_element_name_identifier(span, ast.str(name))
+    }
+  }
+
+  /// Identifiers and static member chains (`Comp`, `icons.Close`) can be an element name.
+  fn expression_element_name(&self, expression: &Expression<'a>) -> Option<JSXElementName<'a>> {
+    let ast = self.ast;
+    match expression {
+      // A lowercase name would re-parse as an intrinsic element
+      Expression::Identifier(ident)
+        if !self.config.codegen || !ident.name.starts_with(|c: char| c.is_ascii_lowercase()) =>
+      {
+        Some(ast.jsx_element_name_identifier_reference(ident.span, ident.name))
+      }
+      Expression::StaticMemberExpression(_) => {
+        let object = self.member_expression_object(expression)?;
+        let JSXMemberExpressionObject::MemberExpression(member) = object else { return None };
+        Some(JSXElementName::MemberExpression(member))
+      }
+      Expression::ParenthesizedExpression(paren) => self.expression_element_name(&paren.expression),
+      _ => None,
+    }
+  }
+
+  fn member_expression_object(
+    &self,
+    expression: &Expression<'a>,
+  ) -> Option<JSXMemberExpressionObject<'a>> {
+    let ast = self.ast;
+    match expression {
+      Expression::Identifier(ident) => {
+        Some(ast.jsx_member_expression_object_identifier_reference(ident.span, ident.name))
+      }
+      Expression::StaticMemberExpression(member) => {
+        let object = self.member_expression_object(&member.object)?;
+        Some(ast.jsx_member_expression_object_member_expression(
+          member.span(),
+          object,
+          ast.jsx_identifier(member.property.span, member.property.name),
+        ))
+      }
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(dynamic_components_vue, "dynamic_components.vue");
+}
@@ -1,72 +1,264 @@
 use memchr::memchr;
-use oxc_ast::ast::JSXAttributeName;
+use oxc_allocator::Vec as ArenaVec;
+use oxc_ast::ast::{
+  ArrayExpressionElement, Expression, JSXAttributeName, JSXAttributeValue, JSXExpression,
+  PropertyKey, PropertyKind, Str,
+};
 use oxc_span::{SPAN, Span};
+use oxc_syntax::identifier::is_identifier_name;
 use vue_compiler_core::parser::Directive;
 
-use crate::parser::{ParserImpl, parse::SourceLocatonSpan};
+use crate::parser::{
+  ParserImpl, VueVersion, elements::kebab_to_case, error, parse::SourceLocatonSpan,
+};
+
+/// Directives handled by the Vue compiler itself, which never resolve to a user binding.
+const BUILTIN_DIRECTIVES: &[&str] = &[
+  "bind", "on", "slot", "for", "if", "else-if", "else", "show", "model", "text", "html", "cloak",
+  "once", "pre", "memo", "is",
+];
+
+/// A directive modifier, like `trim` in `v-model.trim`.
+pub struct DirectiveModifier<'a> {
+  pub name: &'a str,
+  pub span: Span,
+}
 
 impl<'a> ParserImpl<'a> {
   /// Parse directive name
   ///
   /// ### Semantic
   ///  - Treat directive type as namespace, like `v-bind` for `:class="..."`, also for `v-for`, `v-if` which has no params
-  ///  - Treat directive argument, modifiers as attribute name, like `v-bind:class.a.b` -> `class.a.b`
-  pub(crate) fn parse_directive_name(&self, dire: &Directive<'a>) -> JSXAttributeName<'a> {
-    let span = dire.head_loc.span();
-
-    match span.source_text(self.source_text) {
-      name if name.starts_with("v-") => self.analyze_directive_name(name, span),
-      name if name.starts_with(':') => self.analyze_directive_alias(name, span, "v-bind"),
-      name if name.starts_with('@') => self.analyze_directive_alias(name, span, "v-on"),
-      name if name.starts_with('#') => self.analyze_directive_alias(name, span, "v-slot"),
-      // SAFETY: if the directive doesn't start with 'v-', ':', '@', '#', it will be not regarded as a directive by vue-compiler-core
-      _ => unreachable!(),
-    }
-  }
+  ///  - Treat directive argument as attribute name, like `v-bind:class.a.b` -> `class`
+  ///  - Return modifiers separately, each spanning its name, like `a` and `b` of `v-bind:class.a.b`
+  ///
+  /// The `.camel`, `.prop` and `.attr` modifiers of `v-bind` resolve the bound name like Vue's
+  /// compiler does (`:view-box.camel` -> `viewBox`, `:foo.prop` -> `.foo`, `:foo.attr` -> `^foo`),
+  /// and the Vue 2 `.sync` modifier turns the directive into `v-model:foo`. These modifiers are
+  /// consumed, so they are not returned.
+  pub(crate) fn parse_directive_name(
+    &self,
+    dir: &Directive<'a>,
+  ) -> (JSXAttributeName<'a>, Vec<DirectiveModifier<'a>>) {
+    let span = dir.head_loc.span();
+    let head = span.source_text(self.source_text);
 
-  /// For the v-bind:class="..." also for v-model="..." (no params)
-  fn analyze_directive_name(&self, name: &'a str, span: Span) -> JSXAttributeName<'a> {
-    let name_space_span = Span::new(
-      span.start,
-      memchr(b':', name.as_bytes()).map_or(span.end, |i| span.start + i as u32),
-    );
+    let (namespace, namespace_len) = match head.as_bytes().first() {
+      // `.foo` is the shorthand of `:foo.prop`
+      Some(b':' | b'.') => (Str::from("v-bind"), 1),
+      Some(b'@') => (Str::from("v-on"), 1),
+      Some(b'#') => (Str::from("v-slot"), 1),
+      // SAFETY: if the directive doesn't start with 'v-', ':', '@', '#' or '.', it will be not regarded as a directive by vue-compiler-core
+      _ => {
+        let len = "v-".len() + dir.name.len();
+        (self.codegen_directive_identifier(&head[..len]), len)
+      }
+    };
+    let is_shorthand = namespace_len == 1;
 
-    let name_span = if name_space_span == span {
-      // Can't find ':' in the name, so it's not a namespaced name
-      // Such as v-for, v-if, v-else, v-else-if, v-show, v-cloak, v-once, v-pre, v-text, v-html, v-bind, v-on, v-model, v-slot, v-memo, v-transition, v-transition-group, v-custom-directive
-      SPAN
+    // The argument follows the shorthand character or `v-xxx:`
+    let arg_offset = if is_shorthand {
+      1
+    } else if head[namespace_len..].starts_with(':') {
+      namespace_len + 1
     } else {
-      Span::new(name_space_span.end + 1, span.end)
+      // Such as v-for, v-if, v-else, v-show, v-model.trim, v-custom-directive
+      namespace_len
     };
+    let rest = &head[arg_offset..];
+    let has_arg = is_shorthand || arg_offset > namespace_len;
+    let arg_len = if !has_arg {
+      0
+    } else if rest.starts_with('[') {
+      memchr(b']', rest.as_bytes()).map_or(rest.len(), |i| i + 1)
+    } else {
+      memchr(b'.', rest.as_bytes()).unwrap_or(rest.len())
+    };
+    let arg = &rest[..arg_len];
+    let arg_span =
+      if has_arg { Span::sized(span.start + arg_offset as u32, arg_len as u32) } else { SPAN };
 
-    self.ast.jsx_attribute_name_namespaced_name(
+    let mut modifiers = vec![];
+    let mut offset = span.start + (arg_offset + arg_len) as u32;
+    for modifier in rest[arg_len..].split('.').skip(1) {
+      // Skip the `.` separator
+      offset += 1;
+      if !modifier.is_empty() {
+        modifiers.push(DirectiveModifier {
+          name: modifier,
+          span: Span::sized(offset, modifier.len() as u32),
+        });
+      }
+      offset += modifier.len() as u32;
+    }
+
+    let mut namespace = namespace;
+    let mut name = self.codegen_directive_identifier(arg);
+    if namespace == "v-bind" && !arg.is_empty() {
+      // `.sync` writes the value back on `update:foo`, like `v-model:foo`
+      if self.config.vue_version == VueVersion::V2 && take_modifier(&mut modifiers, "sync") {
+        namespace = Str::from("v-model");
+      }
+    }
+    if namespace == "v-bind" && !arg.is_empty() && !arg.starts_with('[') {
+      let is_camel = take_modifier(&mut modifiers, "camel");
+      let is_prop = take_modifier(&mut modifiers, "prop") || head.starts_with('.');
+      let is_attr = take_modifier(&mut modifiers, "attr");
+      let mut resolved = if is_camel { kebab_to_case(arg, false) } else { arg.to_string() };
+      if is_prop {
+        resolved.insert(0, '.');
+      } else if is_attr {
+        resolved.insert(0, '^');
+      }
+      if resolved != arg {
+        name = self.codegen_directive_identifier(self.ast.str(&resolved).as_str());
+      }
+    }
+
+    let name = self.ast.jsx_attribute_name_namespaced_name(
       span,
-      self.ast.jsx_identifier(
-        name_space_span,
-        self.codegen_directive_identifier(name_space_span.source_text(self.source_text)),
-      ),
-      self.ast.jsx_identifier(
-        name_span,
-        self.codegen_directive_identifier(name_span.source_text(self.source_text)),
-      ),
+      self.ast.jsx_identifier(Span::sized(span.start, namespace_len as u32), namespace),
+      self.ast.jsx_identifier(arg_span, name),
+    );
+    (name, modifiers)
+  }
+
+  /// Report the directive syntax introduced by Vue 3, like `v-model:title` and `v-memo`.
+  pub(crate) fn check_vue2_directive(&mut self, dir: &Directive<'a>) {
+    let span = dir.head_loc.span();
+    if dir.name == "model" && dir.argument.is_some() {
+      error::vue3_only_syntax(&mut self.errors, "`v-model` with an argument", span);
+    } else if dir.name == "memo" {
+      error::vue3_only_syntax(&mut self.errors, "`v-memo`", span);
+    }
+  }
+
+  /// Wrap the value of a directive with modifiers as `[value, { trim: true }]`, passing the
+  /// modifiers object like Vue passes `binding.modifiers`. A directive without a value leaves a
+  /// hole, like `[, { prevent: true }]` for `@submit.prevent`.
+  pub(crate) fn wrap_directive_modifiers(
+    &self,
+    value: Option<JSXAttributeValue<'a>>,
+    modifiers: &[DirectiveModifier<'a>],
+  ) -> JSXAttributeValue<'a> {
+    let ast = self.ast;
+    let mut elements = ast.vec();
+    let span = push_directive_value(value, &mut elements);
+    if elements.is_empty() {
+      elements.push(ast.array_expression_element_elision(SPAN));
+    }
+    elements.push(self.directive_modifiers_object(modifiers).into());
+
+    ast.jsx_attribute_value_expression_container(
+      span,
+      JSXExpression::from(ast.expression_array(SPAN, elements)),
     )
   }
 
-  fn analyze_directive_alias(
+  /// Resolve a custom directive (e.g. `v-click-outside`) to its Options API `directives`
+  /// registration, or to its `vClickOutside` (or `VClickOutside`) script binding, following Vue's
+  /// `resolveSetupReference`.
+  ///
+  /// Returns an identifier reference spanning the `v-xxx` directive name, or [`None`] for
+  /// built-in directives and directives which are not declared in any `<script>` block.
+  pub(crate) fn resolve_custom_directive(
+    &self,
+    dir: &Directive<'a>,
+    dir_name: &JSXAttributeName<'a>,
+  ) -> Option<Expression<'a>> {
+    if BUILTIN_DIRECTIVES.contains(&dir.name) {
+      return None;
+    }
+    let JSXAttributeName::NamespacedName(name) = dir_name else {
+      // SAFETY: Directives' props name is always a namespaced name
+      unreachable!()
+    };
+
+    // Locally registered via the Options API `directives: { focus: Focus }` option
+    if let Some(local) = self
+      .options_api
+      .as_ref()
+      .and_then(|component| component.directive(dir.name))
+      .and_then(|directive| directive.local)
+      .filter(|local| self.script_bindings.contains(local))
+    {
+      return Some(self.ast.expression_identifier(name.namespace.span, local));
+    }
+
+    let camel = kebab_to_case(&format!("v-{}", dir.name), false);
+    let pascal = kebab_to_case(&format!("v-{}", dir.name), true);
+    let binding =
+      [camel, pascal].into_iter().find(|name| self.script_bindings.contains(name.as_str()))?;
+
+    Some(self.ast.expression_identifier(name.namespace.span, self.ast.str(&binding)))
+  }
+
+  /// Wrap the value of a resolved custom directive as `[vDirective, value, modifiers]`, mirroring
+  /// the directive tuple Vue passes to `withDirectives`.
+  pub(crate) fn wrap_custom_directive_value(
     &self,
-    name: &'a str,
-    span: Span,
-    full_name: &'a str,
-  ) -> JSXAttributeName<'a> {
-    self.ast.jsx_attribute_name_namespaced_name(
+    reference: Expression<'a>,
+    value: Option<JSXAttributeValue<'a>>,
+    modifiers: &[DirectiveModifier<'a>],
+  ) -> JSXAttributeValue<'a> {
+    let ast = self.ast;
+    let mut elements = ast.vec1(reference.into());
+    let span = push_directive_value(value, &mut elements);
+    if !modifiers.is_empty() {
+      if elements.len() == 1 {
+        elements.push(ast.array_expression_element_elision(SPAN));
+      }
+      elements.push(self.directive_modifiers_object(modifiers).into());
+    }
+
+    ast.jsx_attribute_value_expression_container(
       span,
-      self.ast.jsx_identifier(Span::sized(span.start, 1), full_name),
-      self.ast.jsx_identifier(
-        Span::new(span.start + 1, span.end),
-        self.codegen_directive_identifier(&name[1..]),
-      ),
+      JSXExpression::from(ast.expression_array(SPAN, elements)),
     )
   }
+
+  /// The `{ trim: true }` object of directive modifiers, with each key spanning its modifier.
+  fn directive_modifiers_object(&self, modifiers: &[DirectiveModifier<'a>]) -> Expression<'a> {
+    let ast = self.ast;
+    let properties = ast.vec_from_iter(modifiers.iter().map(|modifier| {
+      let key = if is_identifier_name(modifier.name) {
+        ast.property_key_static_identifier(modifier.span, modifier.name)
+      } else {
+        let raw = ast.str(&format!("\"{}\"", modifier.name));
+        PropertyKey::from(ast.expression_string_literal(modifier.span, modifier.name, Some(raw)))
+      };
+      ast.object_property_kind_object_property(
+        modifier.span,
+        PropertyKind::Init,
+        key,
+        ast.expression_boolean_literal(SPAN, true),
+        false,
+        false,
+        false,
+      )
+    }));
+    ast.expression_object(SPAN, properties)
+  }
+}
+
+/// Push the expression of a directive value, returning the span of its container.
+fn push_directive_value<'a>(
+  value: Option<JSXAttributeValue<'a>>,
+  elements: &mut ArenaVec<'a, ArrayExpressionElement<'a>>,
+) -> Span {
+  let Some(JSXAttributeValue::ExpressionContainer(container)) = value else { return SPAN };
+  let container = container.unbox();
+  if !matches!(container.expression, JSXExpression::EmptyExpression(_)) {
+    elements.push(container.expression.into_expression().into());
+  }
+  container.span
+}
+
+/// Remove the modifier `name`, returning whether it was present.
+fn take_modifier(modifiers: &mut Vec<DirectiveModifier>, name: &str) -> bool {
+  let len = modifiers.len();
+  modifiers.retain(|modifier| modifier.name != name);
+  modifiers.len() != len
 }
 
 #[cfg(test)]
@@ -74,4 +266,12 @@ mod tests {
   use crate::test_ast;
 
   test_ast!(directive_basic_vue, "directive/basic.vue");
+  test_ast!(directive_custom_vue, "directive/custom.vue");
+  test_ast!(directive_custom_export_vue, "directive/custom-export.vue");
+  test_ast!(directive_modifiers_vue, "directive/modifiers.vue");
+  test_ast!(
+    directive_modifiers_vue2_vue,
+    "directive/modifiers-vue2.vue",
+    vue_version: crate::VueVersion::V2
+  );
 }
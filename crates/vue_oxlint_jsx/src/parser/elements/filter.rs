@@ -0,0 +1,90 @@
+use oxc_ast::{NONE, ast::Expression};
+use oxc_span::{SPAN, Span};
+
+use crate::parser::{ParserImpl, VueVersion};
+
+impl<'a> ParserImpl<'a> {
+  /// Parse the expression of an interpolation or `v-bind`, which may be followed by Vue 2 filters.
+  ///
+  /// `a | f | g(b)` is lowered into `g(f(a), b)`, like Vue 2 calls the filters, so the filter
+  /// functions are referenced. In Vue 3, `|` is always a bitwise OR.
+  pub fn parse_filterable_expression(&mut self, span: Span) -> Option<Expression<'a>> {
+    if self.config.vue_version != VueVersion::V2 {
+      return self.parse_pure_expression(span);
+    }
+
+    let mut segments = split_filters(span.source_text(self.source_text))
+      .into_iter()
+      .map(|(start, end)| Span::new(span.start + start, span.start + end));
+    let mut expression = self.parse_pure_expression(segments.next()?)?;
+    for filter in segments {
+      expression = match self.parse_pure_expression(filter)? {
+        // `a | f(b)` passes `a` as the first argument
+        Expression::CallExpression(mut call) => {
+          call.arguments.insert(0, expression.into());
+          Expression::CallExpression(call)
+        }
+        callee => {
+          self.ast.expression_call(SPAN, callee, NONE, self.ast.vec1(expression.into()), false)
+        }
+      };
+    }
+
+    Some(expression)
+  }
+}
+
+/// Split an expression on the top-level `|` of Vue 2 filters, returning the byte ranges of the
+/// expression and each filter.
+///
+/// Like Vue 2's `parseFilters`, `|` inside strings, template literals, regexes and brackets and
+/// the `||` operator are skipped.
+fn split_filters(source: &str) -> Vec<(u32, u32)> {
+  let bytes = source.as_bytes();
+  let mut segments = vec![];
+  let mut quote = None;
+  let mut in_regex = false;
+  let mut depth = 0u32;
+  let mut start = 0;
+
+  for (i, &byte) in bytes.iter().enumerate() {
+    let prev = if i > 0 { bytes[i - 1] } else { 0 };
+    if let Some(q) = quote {
+      if byte == q && prev != b'\\' {
+        quote = None;
+      }
+    } else if in_regex {
+      if byte == b'/' && prev != b'\\' {
+        in_regex = false;
+      }
+    } else {
+      match byte {
+        b'\'' | b'"' | b'`' => quote = Some(byte),
+        b'(' | b'[' | b'{' => depth += 1,
+        b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+        b'|' if depth == 0 && prev != b'|' && bytes.get(i + 1) != Some(&b'|') => {
+          segments.push((start, i as u32));
+          start = i as u32 + 1;
+        }
+        b'/' => {
+          // A `/` after an operand is a division, otherwise it starts a regex
+          let operand = bytes[..i].iter().rev().find(|b| !b.is_ascii_whitespace());
+          in_regex = !operand.is_some_and(|b| {
+            b.is_ascii_alphanumeric() || matches!(b, b')' | b'.' | b'+' | b'-' | b'_' | b'$' | b']')
+          });
+        }
+        _ => {}
+      }
+    }
+  }
+  segments.push((start, bytes.len() as u32));
+
+  segments
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(vue2_filters_vue, "vue2/filters.vue", vue_version: crate::VueVersion::V2);
+}
@@ -1,7 +1,10 @@
 use oxc_allocator::{Allocator, CloneIn, TakeIn, Vec as ArenaVec};
 use oxc_ast::{
   Comment, CommentKind, NONE,
-  ast::{Expression, JSXAttributeItem, JSXChild, JSXExpression, PropertyKind, Statement},
+  ast::{
+    Expression, JSXAttributeItem, JSXAttributeName, JSXChild, JSXExpression, PropertyKind,
+    Statement,
+  },
 };
 use oxc_span::{GetSpanMut, SPAN, Span};
 use vue_compiler_core::parser::{AstNode, Directive, DirectiveArg, ElemProp, Element, SourceNode};
@@ -9,7 +12,7 @@ use vue_compiler_core::parser::{AstNode, Directive, DirectiveArg, ElemProp, Elem
 use crate::{
   is_void_tag,
   parser::{
-    ParserImpl,
+    ParserImpl, VueVersion,
     elements::{
       v_for::VForWrapper,
       v_if::{VIf, VIfManager},
@@ -20,7 +23,11 @@ use crate::{
   },
 };
 
+mod component;
 mod directive;
+mod filter;
+mod slot;
+mod text;
 mod v_for;
 mod v_if;
 mod v_slot;
@@ -44,8 +51,43 @@ fn kebab_to_case(s: &str, pascal: bool) -> String {
   result
 }
 
+/// The raw string literal of an attribute value. It is quoted from the value rather than taken
+/// from the source, as the value may be unquoted (`foo=bar`).
+fn quoted_raw(value: &str) -> String {
+  let quote = if value.contains('"') && !value.contains('\'') { '\'' } else { '"' };
+  let mut raw = String::with_capacity(value.len() + 2);
+  raw.push(quote);
+  for ch in value.chars() {
+    match ch {
+      '\n' => raw.push_str("\\n"),
+      '\r' => raw.push_str("\\r"),
+      _ if ch == quote || ch == '\\' => {
+        raw.push('\\');
+        raw.push(ch);
+      }
+      _ => raw.push(ch),
+    }
+  }
+  raw.push(quote);
+  raw
+}
+
+/// Span of the `v-if` / `v-else-if` attribute among the parsed attributes.
+fn find_v_if_attribute_span(attributes: &[JSXAttributeItem]) -> Option<Span> {
+  attributes.iter().find_map(|attribute| {
+    if let JSXAttributeItem::Attribute(attribute) = attribute
+      && let JSXAttributeName::NamespacedName(name) = &attribute.name
+      && matches!(name.namespace.name.as_str(), "v-if" | "v-else-if")
+    {
+      Some(attribute.span)
+    } else {
+      None
+    }
+  })
+}
+
 impl<'a: 'b, 'b> ParserImpl<'a> {
-  fn parse_children(
+  pub fn parse_children(
     &mut self,
     _start: u32,
     _end: u32,
@@ -61,33 +103,40 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
     for child in children {
       match child {
         AstNode::Element(node) => {
-          let (child, v_if) = self.parse_element(node, None);
+          let (child, v_if, v_for) = self.parse_element(node, None);
 
           if let Some(v_if) = v_if {
-            if let Some(child) = self.add_v_if(child, v_if, &mut v_if_manager) {
-              // There are three cases to return Some(child) for add_v_if function
-              // 1. meet v-else, means the v-if/v-else-if chain is finished
-              // 2. meet v-if while the v_if_manager is not empty, means the previous v-if/v-else-if chain is finished
-              // 3. meet v-else/v-else-if with no v-if, v_if_manager won't add it to the chain, so add it to result there
-              result.push(child);
-            }
+            self.add_v_if(child, v_if, v_for, &mut v_if_manager, &mut result);
           } else {
-            if let Some(chain) = v_if_manager.take_chain() {
-              result.push(chain);
-            }
+            v_if_manager.flush(&mut result);
             result.push(child);
           }
         }
-        AstNode::Text(_) => {}
-        AstNode::Comment(comment) => result.push(self.parse_comment(&comment)),
-        AstNode::Interpolation(interp) => result.push(self.parse_interpolation(&interp)),
+        AstNode::Text(text) => {
+          let is_whitespace = self.text_span(&text).source_text(self.source_text).trim().is_empty();
+          let text = self.parse_text(&text);
+          if is_whitespace {
+            // Whitespace between branches must not break a v-if chain
+            v_if_manager.add_trivia(text, &mut result);
+          } else {
+            v_if_manager.flush(&mut result);
+            result.push(text);
+          }
+        }
+        // Comments between branches are moved into the following branch
+        AstNode::Comment(comment) => {
+          let comment = self.parse_comment(&comment);
+          v_if_manager.add_trivia(comment, &mut result);
+        }
+        AstNode::Interpolation(interp) => {
+          v_if_manager.flush(&mut result);
+          result.push(self.parse_interpolation(&interp));
+        }
       }
     }
 
-    if let Some(chain) = v_if_manager.take_chain() {
-      // If the last element is v-if / v-else-if / v-else, push all the children
-      result.push(chain);
-    }
+    // If the last element is v-if / v-else-if / v-else, push all the children
+    v_if_manager.flush(&mut result);
     result
   }
 
@@ -95,10 +144,14 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
     &mut self,
     node: Element<'a>,
     children: Option<ArenaVec<'a, JSXChild<'a>>>,
-  ) -> (JSXChild<'a>, Option<VIf<'a>>) {
+  ) -> (JSXChild<'a>, Option<VIf<'a>>, Option<VForWrapper<'a>>) {
     let ast = self.ast;
 
-    let open_element_span = {
+    // The elements of a Pug template have no tags, so their head stands for the opening tag
+    let pug_head = self.pug_heads.get(&(node.location.start.offset as u32)).copied();
+    let open_element_span = if let Some(head) = pug_head {
+      head
+    } else {
       let start = node.location.start.offset;
       let tag_name_end = if let Some(prop) = node.properties.last() {
         match prop {
@@ -116,21 +169,32 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
 
     let location_span = node.location.span();
     let tag_name = node.tag_name;
-    let end_element_span = {
-      if location_span.source_text(self.source_text).ends_with("/>") || is_void_tag!(tag_name) {
-        node.location.span()
-      } else {
-        let end = node.location.end.offset;
-        let start =
-          memchr::memrchr(b'<', &self.source_text.as_bytes()[..end]).map(|i| i as u32).unwrap();
-        Span::new(start, end as u32)
-      }
+    let self_closing = match pug_head {
+      Some(head) => head.source_text(self.source_text).ends_with('/'),
+      None => location_span.source_text(self.source_text).ends_with("/>"),
+    };
+    let end_element_span = if pug_head.is_some() {
+      Span::empty(location_span.end)
+    } else if self_closing || is_void_tag!(tag_name) {
+      node.location.span()
+    } else {
+      let end = node.location.end.offset;
+      let start =
+        memchr::memrchr(b'<', &self.source_text.as_bytes()[..end]).map(|i| i as u32).unwrap();
+      Span::new(start, end as u32)
     };
 
     // Use different JSXElementName for component and normal element
     let allocator = Allocator::new();
     let mut element_name = {
-      let name_span = Span::sized(open_element_span.start + 1, node.tag_name.len() as u32);
+      let name_span = match pug_head {
+        // `div` is implicit for `.card`
+        Some(head) if !head.source_text(self.source_text).starts_with(tag_name) => {
+          Span::empty(head.start)
+        }
+        Some(head) => Span::sized(head.start, node.tag_name.len() as u32),
+        None => Span::sized(open_element_span.start + 1, node.tag_name.len() as u32),
+      };
 
       if tag_name.contains('.')
         && let Some(expr) = unsafe {
@@ -149,7 +213,16 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
       {
         // For namespace tag name, e.g. <motion.div />
         jsx_element.opening_element.name.take_in(self.allocator)
-      } else if tag_name.contains('-') || (tag_name == "component" && self.config.codegen) {
+      } else if self.config.custom_elements.is_custom_element(tag_name) {
+        // For <sl-button />, which is a web component
+        ast.jsx_element_name_identifier(name_span, ast.str(tag_name))
+      } else if let Some(builtin) = self.builtin_component_name(tag_name) {
+        // For <KeepAlive />, which is provided by Vue
+        ast.jsx_element_name_identifier(name_span, builtin)
+      } else if let Some(local) = self.local_component_name(name_span, tag_name) {
+        // For <MyButton /> registered with `components: { MyButton: Btn }`
+        local
+      } else if tag_name.contains('-') {
         // For <keep-alive />
         let name = kebab_to_case(tag_name, true);
         ast.jsx_element_name_identifier_reference(name_span, ast.str(&name))
@@ -166,9 +239,12 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
     }
     .clone_in(self.allocator);
 
-    let mut v_for_wrapper = VForWrapper::new(&ast);
+    let mut v_for_wrapper = VForWrapper::new(ast);
     let mut v_slot_wrapper = VSlotWrapper::new(&ast);
     let mut v_if_state: Option<VIf<'a>> = None;
+    if self.config.vue_version == VueVersion::V2 {
+      self.analyze_deprecated_slot(&node, &mut v_slot_wrapper);
+    }
     let mut attributes = ast.vec();
     for prop in node.properties {
       attributes.push(self.parse_prop(
@@ -179,6 +255,23 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
       ));
     }
 
+    // For <component :is="Comp" />, which renders `Comp`
+    if let Some(name) = self.resolve_dynamic_component(tag_name, &mut attributes) {
+      element_name = name;
+    }
+
+    // Vue 2 evaluates `v-for` before `v-if`, so the chain started by this element is evaluated
+    // inside the loop. Otherwise the condition is evaluated outside, without the loop bindings.
+    let v_for_outside_v_if = self.config.vue_version == VueVersion::V2
+      && matches!(v_if_state, Some(VIf::If(_)))
+      && v_for_wrapper.include_v_for();
+    if !v_for_outside_v_if
+      && let Some(VIf::If(condition) | VIf::ElseIf(condition)) = &v_if_state
+      && let Some(span) = find_v_if_attribute_span(&attributes)
+    {
+      self.check_v_if_scope(condition, &v_for_wrapper, span);
+    }
+
     let children = match children {
       Some(children) => children,
       None => v_slot_wrapper.wrap(self.parse_children(
@@ -196,35 +289,54 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
     // - Void tags without />: None
     // - Normal tags with </tag>: closing element with tag name
     // Always use </tag> in codegen mode (prevent tag-hoist in v-slot children)
-    let closing_element =
-      if !self.config.codegen && location_span.source_text(self.source_text).ends_with("/>") {
-        Some(ast.jsx_closing_element(SPAN, ast.jsx_element_name_identifier(SPAN, ast.str(""))))
-      } else if !self.config.codegen && is_void_tag!(tag_name) {
-        None
+    let closing_element = if !self.config.codegen && self_closing {
+      Some(ast.jsx_closing_element(SPAN, ast.jsx_element_name_identifier(SPAN, ast.str(""))))
+    } else if !self.config.codegen && is_void_tag!(tag_name) {
+      None
+    } else {
+      // Normal tag with explicit closing tag or codegen
+      Some(ast.jsx_closing_element(end_element_span, {
+        let span = if pug_head.is_some() {
+          end_element_span
+        } else {
+          Span::sized(end_element_span.start + 2, node.tag_name.len() as u32)
+        };
+        *element_name.span_mut() = span;
+        element_name
+      }))
+    };
+
+    let element = ast.jsx_element(
+      location_span,
+      ast.jsx_opening_element(open_element_span, opening_element_name, NONE, attributes),
+      children,
+      closing_element,
+    );
+
+    // `<slot>` outlets are lowered into a call of the passed slot
+    if tag_name == "slot" {
+      let outlet = self.lower_slot_outlet(element);
+      return if v_for_outside_v_if {
+        let child = ast.jsx_child_expression_container(location_span, outlet.into());
+        (child, v_if_state, Some(v_for_wrapper))
+      } else if v_for_wrapper.include_v_for() {
+        (v_for_wrapper.wrap_expression(outlet), v_if_state, None)
       } else {
-        // Normal tag with explicit closing tag or codegen
-        Some(ast.jsx_closing_element(end_element_span, {
-          let span = Span::sized(end_element_span.start + 2, node.tag_name.len() as u32);
-          *element_name.span_mut() = span;
-          element_name
-        }))
+        (ast.jsx_child_expression_container(location_span, outlet.into()), v_if_state, None)
       };
+    }
 
-    (
-      v_for_wrapper.wrap(ast.jsx_element(
-        location_span,
-        ast.jsx_opening_element(open_element_span, opening_element_name, NONE, attributes),
-        children,
-        closing_element,
-      )),
-      v_if_state,
-    )
+    if v_for_outside_v_if {
+      (JSXChild::Element(ast.alloc(element)), v_if_state, Some(v_for_wrapper))
+    } else {
+      (v_for_wrapper.wrap(element), v_if_state, None)
+    }
   }
 
   fn parse_prop(
     &mut self,
     prop: ElemProp<'a>,
-    v_for_wrapper: &mut VForWrapper<'_, 'a>,
+    v_for_wrapper: &mut VForWrapper<'a>,
     v_slot_wrapper: &mut VSlotWrapper<'_, 'a>,
     v_if_state: &mut Option<VIf<'a>>,
   ) -> JSXAttributeItem<'a> {
@@ -238,8 +350,9 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
           attr_span,
           ast.jsx_attribute_name_identifier(attr.name_loc.span(), ast.str(attr.name)),
           if let Some(value) = attr.value {
+            let start = value.content.raw.as_ptr() as usize - self.source_text.as_ptr() as usize;
             Some(ast.jsx_attribute_value_string_literal(
-              Span::new(value.location.span().start + 1, attr_end - 1),
+              Span::sized(start as u32, value.content.raw.len() as u32),
               ast.str(value.content.raw),
               None,
             ))
@@ -253,7 +366,7 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
         let dir_start = dir.location.start.offset as u32;
         let dir_end = self.roffset(dir.location.end.offset) as u32;
 
-        let dir_name = self.parse_directive_name(&dir);
+        let (dir_name, modifiers) = self.parse_directive_name(&dir);
         // Analyze v-slot and v-for, no matter whether there is an expression
         if dir.name == "slot" {
           self.analyze_v_slot(&dir, v_slot_wrapper, &dir_name);
@@ -267,13 +380,16 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
         if matches!(dir.name, "if" | "else-if") && dir.has_empty_expr() {
           error::v_if_else_without_expression(&mut self.errors, dir.location.span());
         }
+        if self.config.vue_version == VueVersion::V2 {
+          self.check_vue2_directive(&dir);
+        }
 
         // This branch won't return `a=b` attribute but a `...x` struct
         // So we picked this logic into a separate block instead of a elseif branch in the under if-else chain
         if dir.name == "bind"
           && dir.argument.is_none()
           && let Some(expr_node) = &dir.expression
-          && let Some(argument) = self.parse_pure_expression(Span::new(
+          && let Some(argument) = self.parse_filterable_expression(Span::new(
             (expr_node.location.start.offset + 1) as u32,
             dir_end - 1,
           ))
@@ -285,6 +401,7 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
           return ast.jsx_attribute_item_spread_attribute(Span::new(dir_start, dir_end), argument);
         }
 
+        let directive_reference = self.resolve_custom_directive(&dir, &dir_name);
         let value = if let Some(expr) = &dir.expression {
           // +1 to skip the opening quote
           let expr_start = expr.location.start.offset + 1;
@@ -296,7 +413,12 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
                 if matches!(dir.name, "for" | "slot" | "else") {
                   None
                 } else {
-                  let expr = self.parse_pure_expression(Span::new(expr_start as u32, dir_end - 1));
+                  let span = Span::new(expr_start as u32, dir_end - 1);
+                  let expr = if dir.name == "bind" {
+                    self.parse_filterable_expression(span)
+                  } else {
+                    self.parse_pure_expression(span)
+                  };
                   if dir.name == "if" {
                     *v_if_state = expr.map(VIf::If);
                     None
@@ -336,6 +458,19 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
           None
         };
 
+        // Custom directives reference their script binding, e.g. `v-focus` -> `vFocus`
+        // and modifiers are passed along the value, e.g. `@click.stop` -> `[handler, { stop: true }]`
+        let value = match directive_reference {
+          Some(reference) => Some(self.wrap_custom_directive_value(reference, value, &modifiers)),
+          None
+            if !modifiers.is_empty()
+              && !matches!(dir.name, "for" | "slot" | "if" | "else-if" | "else") =>
+          {
+            Some(self.wrap_directive_modifiers(value, &modifiers))
+          }
+          None => value,
+        };
+
         ast.jsx_attribute_item_attribute(
           Span::new(dir_start, dir_end),
           // Attribute Name
@@ -393,7 +528,7 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
     }
   }
 
-  fn parse_comment(&mut self, comment: &SourceNode<'a>) -> JSXChild<'a> {
+  pub fn parse_comment(&mut self, comment: &SourceNode<'a>) -> JSXChild<'a> {
     let ast = self.ast;
     let span = comment.location.span();
     let start = comment.source.as_ptr() as usize - self.source_text.as_ptr() as usize;
@@ -412,15 +547,15 @@ impl<'a: 'b, 'b> ParserImpl<'a> {
 
   fn parse_interpolation(&mut self, introp: &SourceNode<'a>) -> JSXChild<'a> {
     let ast = self.ast;
-    // Use full span for container (includes {{ and }})
+    // Use full span for container (includes the delimiters, like {{ and }})
     let container_span = introp.location.span();
-    // Expression starts after {{ (2 characters)
-    let expr_start = introp.location.start.offset + 2;
+    // Expression starts after the open delimiter, like `{{` or the `#{` of Pug
+    let expr_start = introp.source.as_ptr() as usize - self.source_text.as_ptr() as usize;
 
     ast.jsx_child_expression_container(
       container_span,
       self
-        .parse_pure_expression(Span::new(
+        .parse_filterable_expression(Span::new(
           expr_start as u32,
           (expr_start + introp.source.len()) as u32,
         ))
@@ -0,0 +1,185 @@
+use oxc_allocator::Vec as ArenaVec;
+use oxc_ast::{
+  NONE,
+  ast::{
+    Argument, Expression, JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXAttributeValue,
+    JSXElement, JSXExpression, LogicalOperator, ObjectPropertyKind, PropertyKey, PropertyKind, Str,
+  },
+};
+use oxc_span::{SPAN, Span};
+use oxc_syntax::identifier::is_identifier_name;
+
+use crate::parser::{
+  ParserImpl,
+  elements::{kebab_to_case, quoted_raw},
+};
+
+impl<'a> ParserImpl<'a> {
+  /// Lower a `<slot>` outlet into `$slots[name]?.(props) ?? <>fallback</>`, so the bound props
+  /// are tied to the slot they are passed to.
+  ///
+  /// The `$slots` reference spans the `slot` tag name and the props object spans the opening tag.
+  pub fn lower_slot_outlet(&self, element: JSXElement<'a>) -> Expression<'a> {
+    let ast = self.ast;
+    let opening_element = element.opening_element.unbox();
+    let tag_span = Span::sized(opening_element.span.start + 1, "slot".len() as u32);
+
+    let mut name = None;
+    let mut props = ast.vec();
+    for attribute in opening_element.attributes {
+      match attribute {
+        // `v-bind="obj"` spreads the object into the slot props
+        JSXAttributeItem::SpreadAttribute(spread) => {
+          let spread = spread.unbox();
+          props.push(ast.object_property_kind_spread_property(spread.span, spread.argument));
+        }
+        JSXAttributeItem::Attribute(attribute) => {
+          self.add_slot_outlet_prop(attribute.unbox(), &mut name, &mut props);
+        }
+      }
+    }
+
+    let slots = ast.expression_identifier(tag_span, "$slots");
+    let callee = match name {
+      Some(SlotName::Static(span, name)) if is_identifier_name(&name) => Expression::from(
+        ast.member_expression_static(SPAN, slots, ast.identifier_name(span, name), false),
+      ),
+      Some(SlotName::Static(span, name)) => Expression::from(ast.member_expression_computed(
+        SPAN,
+        slots,
+        ast.expression_string_literal(span, name, Some(ast.str(&quoted_raw(&name)))),
+        false,
+      )),
+      Some(SlotName::Dynamic(expression)) => {
+        Expression::from(ast.member_expression_computed(SPAN, slots, expression, false))
+      }
+      None => Expression::from(ast.member_expression_static(
+        SPAN,
+        slots,
+        ast.identifier_name(SPAN, "default"),
+        false,
+      )),
+    };
+
+    let call = ast.expression_chain(
+      SPAN,
+      ast.chain_element_call_expression(
+        SPAN,
+        callee,
+        NONE,
+        ast.vec1(Argument::from(ast.expression_object(opening_element.span, props))),
+        true,
+      ),
+    );
+
+    // Fallback content is rendered when the slot is not passed
+    if element.children.is_empty() {
+      call
+    } else {
+      ast.expression_logical(
+        SPAN,
+        call,
+        LogicalOperator::Coalesce,
+        ast.expression_jsx_fragment(
+          SPAN,
+          ast.jsx_opening_fragment(SPAN),
+          element.children,
+          ast.jsx_closing_fragment(SPAN),
+        ),
+      )
+    }
+  }
+
+  fn add_slot_outlet_prop(
+    &self,
+    attribute: JSXAttribute<'a>,
+    name: &mut Option<SlotName<'a>>,
+    props: &mut ArenaVec<'a, ObjectPropertyKind<'a>>,
+  ) {
+    let ast = self.ast;
+    let (key_span, key, value) = match attribute.name {
+      // Static attributes are passed as strings, `<slot foo>` passes an empty string
+      JSXAttributeName::Identifier(ident) => {
+        let value = match attribute.value {
+          Some(JSXAttributeValue::StringLiteral(lit)) => {
+            if ident.name == "name" {
+              *name = Some(SlotName::Static(lit.span, lit.value));
+              return;
+            }
+            ast.expression_string_literal(
+              lit.span,
+              lit.value,
+              Some(ast.str(&quoted_raw(&lit.value))),
+            )
+          }
+          _ => ast.expression_string_literal(SPAN, "", Some(ast.str("\"\""))),
+        };
+        (ident.span, kebab_to_case(&ident.name, false), value)
+      }
+      JSXAttributeName::NamespacedName(namespaced) => {
+        let Some(JSXAttributeValue::ExpressionContainer(container)) = attribute.value else {
+          return;
+        };
+        let container = container.unbox();
+        if matches!(container.expression, JSXExpression::EmptyExpression(_)) {
+          return;
+        }
+        let value = container.expression.into_expression();
+
+        let arg_span = namespaced.name.span;
+        let arg = arg_span.source_text(self.source_text);
+        match namespaced.namespace.name.as_str() {
+          "v-bind" if arg.starts_with('[') => {
+            // `:[key]="value"` is already lowered into `{ [key]: value }`
+            if let Expression::ObjectExpression(object) = value {
+              props.extend(object.unbox().properties);
+            }
+            return;
+          }
+          "v-bind" if arg == "name" => {
+            *name = Some(SlotName::Dynamic(value));
+            return;
+          }
+          "v-bind" => (arg_span, kebab_to_case(arg, false), value),
+          // Listeners are passed as `onXxx` props
+          "v-on" if !arg.starts_with('[') => {
+            (arg_span, format!("on{}", kebab_to_case(arg, true)), value)
+          }
+          // Other directives are not passed to the slot
+          _ => return,
+        }
+      }
+    };
+
+    let key = if is_identifier_name(&key) {
+      ast.property_key_static_identifier(key_span, ast.str(&key))
+    } else {
+      let raw = ast.str(&format!("\"{key}\""));
+      PropertyKey::from(ast.expression_string_literal(key_span, ast.str(&key), Some(raw)))
+    };
+    props.push(ast.object_property_kind_object_property(
+      attribute.span,
+      PropertyKind::Init,
+      key,
+      value,
+      false,
+      false,
+      false,
+    ));
+  }
+}
+
+enum SlotName<'a> {
+  /// `name="item"`, with the span of the value
+  Static(Span, Str<'a>),
+  /// `:name="expr"`
+  Dynamic(Expression<'a>),
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(slot_vue, "slot.vue");
+  test_ast!(slot_unquoted_vue, "slot_unquoted.vue");
+}
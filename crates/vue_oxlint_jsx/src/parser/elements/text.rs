@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use oxc_ast::ast::JSXChild;
+use oxc_span::Span;
+use oxc_syntax::xml_entities::XML_ENTITIES;
+use vue_compiler_core::parser::TextNode;
+
+use crate::parser::{ParserImpl, parse::SourceLocatonSpan};
+
+impl<'a> ParserImpl<'a> {
+  /// Template text is kept as `JSXText`, so rules checking literal text work on Vue files.
+  pub fn parse_text(&self, text: &TextNode<'a>) -> JSXChild<'a> {
+    let span = self.text_span(text);
+    let raw = span.source_text(self.source_text);
+
+    let value = if self.config.codegen {
+      self.codegen_jsx_text(raw)
+    } else {
+      match decode_entities(raw) {
+        Cow::Borrowed(value) => value.into(),
+        Cow::Owned(value) => self.ast.str(&value),
+      }
+    };
+
+    self.ast.jsx_child_text(span, value, Some(raw.into()))
+  }
+
+  /// The span of the text tokens. The node location is not advanced for text ending at EOF.
+  pub fn text_span(&self, text: &TextNode<'a>) -> Span {
+    let (Some(first), Some(last)) = (text.text.first(), text.text.last()) else {
+      return text.location.span();
+    };
+    let start = first.raw.as_ptr() as usize - self.source_text.as_ptr() as usize;
+    let end = last.raw.as_ptr() as usize - self.source_text.as_ptr() as usize + last.raw.len();
+    Span::new(start as u32, end as u32)
+  }
+}
+
+/// Decode the HTML character references (`&lt;`, `&#123;`, `&#x7B;`) of template text.
+///
+/// References without the trailing `;` are kept as written.
+fn decode_entities(raw: &str) -> Cow<'_, str> {
+  if !raw.contains('&') {
+    return Cow::Borrowed(raw);
+  }
+
+  let mut result = String::with_capacity(raw.len());
+  let mut rest = raw;
+  while let Some(index) = rest.find('&') {
+    result.push_str(&rest[..index]);
+    rest = &rest[index..];
+
+    let decoded =
+      rest.find(';').and_then(|end| decode_reference(&rest[1..end]).map(|ch| (ch, end + 1)));
+
+    if let Some((ch, len)) = decoded {
+      result.push(ch);
+      rest = &rest[len..];
+    } else {
+      result.push('&');
+      rest = &rest[1..];
+    }
+  }
+  result.push_str(rest);
+
+  Cow::Owned(result)
+}
+
+/// Decode the name of a character reference, without the leading `&` and trailing `;`.
+fn decode_reference(name: &str) -> Option<char> {
+  let Some(number) = name.strip_prefix('#') else {
+    return XML_ENTITIES.get(name).copied();
+  };
+  let code = match number.strip_prefix(['x', 'X']) {
+    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+    None if number.bytes().all(|b| b.is_ascii_digit()) => number.parse().ok()?,
+    None => return None,
+  };
+  char::from_u32(code)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(text_vue, "text.vue");
+}
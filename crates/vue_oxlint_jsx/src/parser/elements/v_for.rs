@@ -2,19 +2,21 @@ use oxc_allocator::{Allocator, CloneIn, TakeIn};
 use oxc_ast::{
   AstBuilder, NONE,
   ast::{
-    Argument, Expression, FormalParameters, JSXChild, JSXElement, JSXExpression,
-    ParenthesizedExpression,
+    Argument, ArrowFunctionExpression, BindingIdentifier, Expression, FormalParameters, Function,
+    IdentifierReference, JSXChild, JSXElement, JSXExpression, ParenthesizedExpression,
   },
 };
 
+use oxc_ast_visit::{Visit, walk};
 use oxc_span::{SPAN, Span};
+use oxc_syntax::scope::ScopeFlags;
 use regex::Regex;
 use vue_compiler_core::parser::Directive;
 
 use crate::parser::{ParserImpl, error, parse::SourceLocatonSpan};
 
-pub struct VForWrapper<'a, 'b> {
-  ast: &'a AstBuilder<'b>,
+pub struct VForWrapper<'b> {
+  ast: AstBuilder<'b>,
   data_origin: Option<ParenthesizedExpression<'b>>,
   params: Option<FormalParameters<'b>>,
 }
@@ -25,7 +27,29 @@ impl<'a> ParserImpl<'a> {
     None
   }
 
-  pub fn analyze_v_for(&mut self, dir: &Directive<'a>, wrapper: &mut VForWrapper<'_, 'a>) {
+  /// Report the `v-for` bindings referenced by a `v-if` / `v-else-if` condition which is evaluated
+  /// outside of the loop, mapped to the `span` of the attribute.
+  pub fn check_v_if_scope(
+    &mut self,
+    condition: &Expression<'a>,
+    wrapper: &VForWrapper<'a>,
+    span: Span,
+  ) {
+    let bindings = wrapper.binding_names();
+    if bindings.is_empty() {
+      return;
+    }
+
+    let mut collector = ReferenceCollector::default();
+    collector.visit_expression(condition);
+    for name in collector.names {
+      if bindings.contains(&name) {
+        error::v_if_uses_v_for_binding(&mut self.errors, name, span);
+      }
+    }
+  }
+
+  pub fn analyze_v_for(&mut self, dir: &Directive<'a>, wrapper: &mut VForWrapper<'a>) {
     (|| {
       if dir.has_empty_expr() {
         self.invalid_v_for_expression(dir.location.span())?;
@@ -81,24 +105,35 @@ impl<'a> ParserImpl<'a> {
 }
 
 /// Wrap the JSX element with a function call, similar to jsx {items.map(items => <div key={item.id} />)} but with vue semantic.
-impl<'a, 'b> VForWrapper<'a, 'b> {
-  pub const fn new(ast: &'a AstBuilder<'b>) -> Self {
+impl<'b> VForWrapper<'b> {
+  pub const fn new(ast: AstBuilder<'b>) -> Self {
     Self { ast, data_origin: None, params: None }
   }
 
   pub fn wrap(self, element: JSXElement<'b>) -> JSXChild<'b> {
     if self.include_v_for() {
-      let Self { ast, data_origin, params } = self;
-      let data_origin = data_origin.unwrap();
-      let params = params.unwrap();
+      let element = Expression::JSXElement(self.ast.alloc(element));
+      self.wrap_expression(element)
+    } else {
+      JSXChild::Element(self.ast.alloc(element))
+    }
+  }
 
-      ast.jsx_child_expression_container(
+  /// Wrap any expression as the loop body, e.g. a whole `v-if` chain with Vue 2 precedence.
+  pub fn wrap_expression(self, expression: Expression<'b>) -> JSXChild<'b> {
+    let Self { ast, data_origin, params } = self;
+    // SAFETY: only called when `include_v_for` is true
+    let data_origin = data_origin.unwrap();
+    let params = params.unwrap();
+
+    ast.jsx_child_expression_container(
+      SPAN,
+      JSXExpression::CallExpression(ast.alloc_call_expression(
         SPAN,
-        JSXExpression::CallExpression(ast.alloc_call_expression(
-          SPAN,
-          Expression::ParenthesizedExpression(ast.alloc(data_origin)),
-          NONE,
-          self.ast.vec1(Argument::ArrowFunctionExpression(ast.alloc_arrow_function_expression(
+        Expression::ParenthesizedExpression(ast.alloc(data_origin)),
+        NONE,
+        ast.vec1(Argument::ArrowFunctionExpression(
+          ast.alloc_arrow_function_expression(
             SPAN,
             true,
             false,
@@ -108,23 +143,20 @@ impl<'a, 'b> VForWrapper<'a, 'b> {
             ast.function_body(
               SPAN,
               ast.vec(),
-              ast.vec1(ast.statement_expression(
-                SPAN,
-                ast.expression_parenthesized(SPAN, Expression::JSXElement(self.ast.alloc(element))),
-              )),
+              ast.vec1(
+                ast.statement_expression(SPAN, ast.expression_parenthesized(SPAN, expression)),
+              ),
             ),
-          ))),
-          false,
+          ),
         )),
-      )
-    } else {
-      JSXChild::Element(self.ast.alloc(element))
-    }
+        false,
+      )),
+    )
   }
 }
 
-impl<'b> VForWrapper<'_, 'b> {
-  const fn include_v_for(&self) -> bool {
+impl<'b> VForWrapper<'b> {
+  pub const fn include_v_for(&self) -> bool {
     self.data_origin.is_some() && self.params.is_some()
   }
 
@@ -135,6 +167,57 @@ impl<'b> VForWrapper<'_, 'b> {
   const fn set_params(&mut self, params: FormalParameters<'b>) {
     self.params = Some(params);
   }
+
+  /// Names of the bindings declared by the `v-for` alias, e.g. `item` and `index`.
+  pub fn binding_names(&self) -> Vec<&'b str> {
+    let Some(params) = &self.params else { return vec![] };
+    let mut names = vec![];
+    for param in &params.items {
+      names.extend(param.pattern.get_binding_identifiers().iter().map(|ident| ident.name.as_str()));
+    }
+    if let Some(rest) = &params.rest {
+      names.extend(
+        rest.rest.argument.get_binding_identifiers().iter().map(|ident| ident.name.as_str()),
+      );
+    }
+    names
+  }
+}
+
+/// Collect the names of the free identifier references in an expression, in order and
+/// deduplicated, skipping the names bound by its own functions, like `u` of `list.some(u => u)`.
+#[derive(Default)]
+struct ReferenceCollector<'a> {
+  names: Vec<&'a str>,
+  /// The names bound in each function entered, parameters first.
+  scopes: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Visit<'a> for ReferenceCollector<'a> {
+  fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+    let name = it.name.as_str();
+    if !self.names.contains(&name) && !self.scopes.iter().any(|scope| scope.contains(&name)) {
+      self.names.push(name);
+    }
+  }
+
+  fn visit_binding_identifier(&mut self, it: &BindingIdentifier<'a>) {
+    if let Some(scope) = self.scopes.last_mut() {
+      scope.push(it.name.as_str());
+    }
+  }
+
+  fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+    self.scopes.push(vec![]);
+    walk::walk_arrow_function_expression(self, it);
+    self.scopes.pop();
+  }
+
+  fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+    self.scopes.push(vec![]);
+    walk::walk_function(self, it, flags);
+    self.scopes.pop();
+  }
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 use std::mem::take;
 
+use oxc_allocator::Vec as ArenaVec;
 use oxc_ast::{
   AstBuilder,
   ast::{Expression, JSXChild},
@@ -7,7 +8,7 @@ use oxc_ast::{
 
 use oxc_span::{GetSpan, SPAN};
 
-use crate::parser::{ParserImpl, error};
+use crate::parser::{ParserImpl, elements::v_for::VForWrapper, error};
 
 pub enum VIf<'a> {
   If(Expression<'a>),
@@ -30,7 +31,11 @@ impl<'a> Into<Expression<'a>> for VIf<'a> {
 // The manager of v-if / v-else-if / v-else, different from the wrapper, it works across multiple elements
 pub struct VIfManager<'a, 'b> {
   ast: &'a AstBuilder<'b>,
-  chain: Vec<(JSXChild<'b>, VIf<'b>)>, // child, v_if
+  chain: Vec<(Vec<JSXChild<'b>>, JSXChild<'b>, VIf<'b>)>, // leading comments, child, v_if
+  /// Comments and whitespace met while the chain is open, moved into the next branch like Vue does
+  comments: Vec<JSXChild<'b>>,
+  /// With Vue 2 precedence, the `v-for` of the chain head wraps the whole chain
+  v_for: Option<VForWrapper<'b>>,
 }
 
 impl<'a> ParserImpl<'a> {
@@ -38,40 +43,55 @@ impl<'a> ParserImpl<'a> {
     &mut self,
     child: JSXChild<'a>,
     v_if: VIf<'a>,
+    v_for: Option<VForWrapper<'a>>,
     manager: &mut VIfManager<'_, 'a>,
-  ) -> Option<JSXChild<'a>> {
+    result: &mut ArenaVec<'a, JSXChild<'a>>,
+  ) {
     if matches!(v_if, VIf::If(_)) {
-      if manager.chain.is_empty() {
-        manager.chain.push((child, v_if));
-        None
-      } else {
-        // The previous v-if/v-else-if chain is finished
-        let result = manager.take_chain();
-        manager.chain.push((child, v_if));
-        result
-      }
+      // The previous v-if/v-else-if chain (if any) is finished
+      manager.flush(result);
+      manager.chain.push((vec![], child, v_if));
+      manager.v_for = v_for;
     } else if manager.chain.is_empty() {
       // Orphan v-else-if / v-else
       // https://play.vuejs.org/#eNp9kLFuwjAQhl/FuhnC0E4ordRWDO3QVi2jlyg5gsGxLd85REJ5d2wjAgNis/7v8+m/O8Kbc0UfEJZQMnZOV4yv0ghRNqoX/Rw14VxtXiSwDyhBLCItF5MKM2CqrdmottiRNXHOMX2XUNvOKY3+x7GyhiQsRSaJVVrbw1fO0tjZJa+3WO/v5DsaUibh1yOh72ORiXHlW+QzXv1/4xDfE+xsE3S0H8A/JKtD6njW3oNpYu0bL7f97Jz1rEy7ptXAaOiyVL5LNMfsS4jH/Hiw+rXuU/Gc/0kzwngCD9Z/dQ==
       error::v_else_without_adjacent_if(&mut self.errors, child.span());
-      Some(child)
-    } else if matches!(v_if, VIf::Else) {
-      manager.chain.push((child, v_if));
-      // The chain is finished, return the result directly, for possible next node
-      manager.take_chain()
+      result.push(child);
     } else {
-      manager.chain.push((child, v_if));
-      None
+      let is_else = matches!(v_if, VIf::Else);
+      manager.chain.push((take(&mut manager.comments), child, v_if));
+      if is_else {
+        // The chain is finished
+        manager.flush(result);
+      }
     }
   }
 }
 
 impl<'a, 'b> VIfManager<'a, 'b> {
   pub const fn new(ast: &'a AstBuilder<'b>) -> Self {
-    Self { ast, chain: vec![] }
+    Self { ast, chain: vec![], comments: vec![], v_for: None }
+  }
+
+  /// Add a template comment or whitespace text, deferring it while a chain is open as it may sit
+  /// between two branches.
+  pub fn add_trivia(&mut self, comment: JSXChild<'b>, result: &mut ArenaVec<'b, JSXChild<'b>>) {
+    if self.chain.is_empty() {
+      result.push(comment);
+    } else {
+      self.comments.push(comment);
+    }
+  }
+
+  /// Finish the open chain (if any), pushing it and the comments following it to `result`.
+  pub fn flush(&mut self, result: &mut ArenaVec<'b, JSXChild<'b>>) {
+    if let Some(chain) = self.take_chain() {
+      result.push(chain);
+    }
+    result.extend(self.comments.drain(..));
   }
 
-  pub fn take_chain(&mut self) -> Option<JSXChild<'b>> {
+  fn take_chain(&mut self) -> Option<JSXChild<'b>> {
     if self.chain.is_empty() {
       // No chain exists
       return None;
@@ -81,30 +101,40 @@ impl<'a, 'b> VIfManager<'a, 'b> {
     let mut chain_stack = take(&mut self.chain);
 
     // SAFETY: chain_stack is not empty
-    let last = if matches!(chain_stack.last().unwrap().1, VIf::Else) {
-      self.build_jsx_fragment_expression(chain_stack.pop().unwrap().0)
+    let last = if matches!(chain_stack.last().unwrap().2, VIf::Else) {
+      let (comments, child, _) = chain_stack.pop().unwrap();
+      self.build_jsx_fragment_expression(comments, child)
     } else {
       ast.expression_identifier(SPAN, "undefined")
     };
 
     let mut result = last;
-    while let Some((child, v_if)) = chain_stack.pop() {
+    while let Some((comments, child, v_if)) = chain_stack.pop() {
       result = ast.expression_conditional(
         SPAN,
         v_if.into(),
-        self.build_jsx_fragment_expression(child),
+        self.build_jsx_fragment_expression(comments, child),
         result,
       );
     }
 
-    Some(ast.jsx_child_expression_container(SPAN, result.into()))
+    Some(match self.v_for.take() {
+      Some(v_for) => v_for.wrap_expression(result),
+      None => ast.jsx_child_expression_container(SPAN, result.into()),
+    })
   }
 
-  fn build_jsx_fragment_expression(&self, child: JSXChild<'b>) -> Expression<'b> {
+  fn build_jsx_fragment_expression(
+    &self,
+    comments: Vec<JSXChild<'b>>,
+    child: JSXChild<'b>,
+  ) -> Expression<'b> {
+    let mut children = self.ast.vec_from_iter(comments);
+    children.push(child);
     self.ast.expression_jsx_fragment(
       SPAN,
       self.ast.jsx_opening_fragment(SPAN),
-      self.ast.vec1(child),
+      children,
       self.ast.jsx_closing_fragment(SPAN),
     )
   }
@@ -116,4 +146,8 @@ mod tests {
 
   test_ast!(v_if_vue, "directive/v-if.vue");
   test_ast!(v_if_error_vue, "directive/v-if-error.vue", true, false);
+  test_ast!(v_if_comments_vue, "directive/v-if-comments.vue");
+  test_ast!(v_if_v_for_vue, "directive/v-if-v-for.vue");
+  test_ast!(v_if_v_for_vue2_vue, "directive/v-if-v-for-vue2.vue", vue_version: crate::VueVersion::V2);
+  test_ast!(v_if_v_for_error_vue, "directive/v-if-v-for-error.vue", true, false);
 }
@@ -7,9 +7,14 @@ use oxc_ast::{
   },
 };
 use oxc_span::{SPAN, Span};
-use vue_compiler_core::parser::Directive;
+use oxc_syntax::identifier::is_identifier_name;
+use vue_compiler_core::parser::{Directive, DirectiveArg, ElemProp, Element};
 
-use crate::parser::{ParserImpl, parse::SourceLocatonSpan};
+use crate::parser::{
+  ParserImpl,
+  elements::quoted_raw,
+  parse::{SourceLocatonSpan, attr_value_span},
+};
 
 pub struct VSlotWrapper<'a, 'b> {
   ast: &'a AstBuilder<'b>,
@@ -72,27 +77,103 @@ impl<'a> ParserImpl<'a> {
         ));
       } else {
         let expr = dir.expression.as_ref().unwrap();
-        let allocator = Allocator::new();
-        // SAFETY: warp with `((` and `)=>0)`
-        let Expression::ArrowFunctionExpression(mut arrow_function_expression) = (unsafe {
-          self.parse_expression(
-            Span::sized(expr.location.span().start + 1, expr.content.raw.len() as u32),
-            b"((",
-            b")=>0)",
-            &allocator,
-          )?
-        }) else {
-          // SAFETY: We always wrap the source in arrow function expression
-          unreachable!()
-        };
-        wrapper.set_params(
-          arrow_function_expression.params.take_in(&allocator).clone_in(self.allocator),
-        );
+        wrapper.set_params(self.parse_slot_params(Span::sized(
+          expr.location.span().start + 1,
+          expr.content.raw.len() as u32,
+        ))?);
       }
 
       Some(())
     })();
   }
+
+  /// Vue 2 slot syntax: `slot="name"` (or `:slot="name"`) with `slot-scope="params"` (or `scope`
+  /// on `<template>`), which is handled like `v-slot:name="params"`.
+  ///
+  /// Without a scope, only `<template slot="name">` passes its children as a slot. On other
+  /// elements `slot` is kept as a plain attribute, like Vue 2 does for native shadow DOM slots.
+  pub fn analyze_deprecated_slot(
+    &mut self,
+    node: &Element<'a>,
+    wrapper: &mut VSlotWrapper<'_, 'a>,
+  ) {
+    let is_template = node.tag_name == "template";
+    let mut name = None;
+    let mut scope = None;
+    for prop in &node.properties {
+      match prop {
+        ElemProp::Attr(attr) if attr.name == "slot" => {
+          name = attr
+            .value
+            .as_ref()
+            .map(|value| SlotAttribute::Static(attr_value_span(value), value.content.raw));
+        }
+        ElemProp::Attr(attr)
+          if attr.name == "slot-scope" || (is_template && attr.name == "scope") =>
+        {
+          scope = Some(attr.value.as_ref().map(attr_value_span));
+        }
+        ElemProp::Dir(dir)
+          if dir.name == "bind"
+            && matches!(dir.argument, Some(DirectiveArg::Static("slot")))
+            && !dir.has_empty_expr() =>
+        {
+          name = dir.expression.as_ref().map(|expr| SlotAttribute::Dynamic(attr_value_span(expr)));
+        }
+        _ => {}
+      }
+    }
+    if scope.is_none() && !(is_template && name.is_some()) {
+      return;
+    }
+
+    let ast = self.ast;
+    let (key, is_computed) = match name {
+      Some(SlotAttribute::Static(span, name)) if is_identifier_name(name) => {
+        (ast.property_key_static_identifier(span, name), false)
+      }
+      Some(SlotAttribute::Static(span, name)) => {
+        let raw = ast.str(&quoted_raw(name));
+        (PropertyKey::from(ast.expression_string_literal(span, name, Some(raw))), false)
+      }
+      Some(SlotAttribute::Dynamic(span)) => {
+        let Some(expression) = self.parse_pure_expression(span) else { return };
+        (PropertyKey::from(expression), true)
+      }
+      None => (ast.property_key_static_identifier(SPAN, "default"), false),
+    };
+    let params = match scope.flatten() {
+      Some(span) if !span.source_text(self.source_text).trim().is_empty() => {
+        let Some(params) = self.parse_slot_params(span) else { return };
+        params
+      }
+      _ => ast.formal_parameters(SPAN, FormalParameterKind::ArrowFormalParameters, ast.vec(), NONE),
+    };
+
+    wrapper.set_key(key);
+    wrapper.set_is_computed(is_computed);
+    wrapper.set_params(params);
+  }
+
+  /// Parse slot params like `{ item }` of `v-slot="{ item }"`.
+  fn parse_slot_params(&mut self, span: Span) -> Option<FormalParameters<'a>> {
+    let allocator = Allocator::new();
+    // SAFETY: warp with `((` and `)=>0)`
+    let Expression::ArrowFunctionExpression(mut arrow_function_expression) =
+      (unsafe { self.parse_expression(span, b"((", b")=>0)", &allocator)? })
+    else {
+      // SAFETY: We always wrap the source in arrow function expression
+      unreachable!()
+    };
+    Some(arrow_function_expression.params.take_in(&allocator).clone_in(self.allocator))
+  }
+}
+
+enum SlotAttribute<'a> {
+  /// `slot="name"`, with the span of the value
+  Static(Span, &'a str),
+  /// `:slot="expr"`, with the span of the expression
+  Dynamic(Span),
 }
 
 impl<'a, 'b> VSlotWrapper<'a, 'b> {
@@ -171,4 +252,10 @@ mod tests {
   use crate::test_ast;
 
   test_ast!(v_slot_vue, "directive/v-slot.vue");
+  test_ast!(vue2_slots_vue, "vue2/slots.vue", vue_version: crate::VueVersion::V2);
+  test_ast!(
+    vue2_slots_unquoted_vue,
+    "vue2/slots_unquoted.vue",
+    vue_version: crate::VueVersion::V2
+  );
 }
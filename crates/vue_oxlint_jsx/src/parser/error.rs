@@ -1,28 +1,33 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, io};
 
 use oxc_span::Span;
 
 use oxc_diagnostics::OxcDiagnostic;
 use vue_compiler_core::error::{CompilationError, CompilationErrorKind, ErrorHandler};
 
-use crate::parser::parse::SourceLocatonSpan;
+use crate::parser::parse::{ByteOffsets, SourceLocatonSpan};
 
 pub struct OxcErrorHandler<'a> {
   errors: &'a RefCell<&'a mut Vec<OxcDiagnostic>>,
   panicked: &'a RefCell<bool>,
+  offsets: Option<&'a ByteOffsets>,
 }
 
 impl<'a> OxcErrorHandler<'a> {
   pub const fn new(
     errors: &'a RefCell<&'a mut Vec<OxcDiagnostic>>,
     panicked: &'a RefCell<bool>,
+    offsets: Option<&'a ByteOffsets>,
   ) -> Self {
-    Self { errors, panicked }
+    Self { errors, panicked, offsets }
   }
 }
 
 impl ErrorHandler for OxcErrorHandler<'_> {
-  fn on_error(&self, error: CompilationError) {
+  fn on_error(&self, mut error: CompilationError) {
+    if let Some(offsets) = self.offsets {
+      offsets.convert(&mut error.location);
+    }
     if !is_warn(&error) && !*self.panicked.borrow() {
       if should_panic(&error) {
         *self.panicked.borrow_mut() = true;
@@ -96,6 +101,39 @@ pub fn multiple_script_setup_tags(errors: &mut Vec<OxcDiagnostic>, span: Span) {
   );
 }
 
+#[cold]
+pub fn invalid_script_setup_generic(errors: &mut Vec<OxcDiagnostic>, span: Span) {
+  errors.push(
+    OxcDiagnostic::error("Invalid type parameters in <script setup> `generic` attribute.")
+      .with_label(span),
+  );
+}
+
+#[cold]
+pub fn script_setup_exports(errors: &mut Vec<OxcDiagnostic>, span: Span) {
+  errors.push(
+    OxcDiagnostic::error(
+      "<script setup> cannot contain ES module exports. If you are using a previous version of <script setup>, please consult the updated RFC at https://github.com/vuejs/rfcs/pull/227.",
+    )
+    .with_label(span),
+  );
+}
+
+#[cold]
+pub fn script_setup_src(errors: &mut Vec<OxcDiagnostic>, span: Span) {
+  errors.push(
+    OxcDiagnostic::error(
+      "<script setup> cannot use the \"src\" attribute because its syntax will be ambiguous outside of the component.",
+    )
+    .with_label(span),
+  );
+}
+
+#[cold]
+pub fn unresolved_src(errors: &mut Vec<OxcDiagnostic>, src: &str, err: &io::Error, span: Span) {
+  errors.push(OxcDiagnostic::error(format!("Cannot resolve `{src}`: {err}.")).with_label(span));
+}
+
 #[cold]
 pub fn v_else_without_adjacent_if(errors: &mut Vec<OxcDiagnostic>, span: Span) {
   errors.push(
@@ -112,3 +150,65 @@ pub fn invalid_v_for_expression(errors: &mut Vec<OxcDiagnostic>, span: Span) {
 pub fn v_if_else_without_expression(errors: &mut Vec<OxcDiagnostic>, span: Span) {
   errors.push(OxcDiagnostic::error("v-if/v-else-if is missing expression.").with_label(span));
 }
+
+#[cold]
+pub fn v_if_uses_v_for_binding(errors: &mut Vec<OxcDiagnostic>, name: &str, span: Span) {
+  errors.push(
+    OxcDiagnostic::error(format!(
+      "`{name}` is declared by v-for, but v-if has a higher priority than v-for on the same element, so it is not in scope."
+    ))
+    .with_help("Move v-if to a wrapper <template> element, or filter the list with a computed property.")
+    .with_label(span),
+  );
+}
+
+#[cold]
+pub fn removed_listeners(errors: &mut Vec<OxcDiagnostic>, span: Span) {
+  errors.push(
+    OxcDiagnostic::error("`$listeners` has been removed in Vue 3.")
+      .with_help("Listeners are part of `$attrs` in Vue 3.")
+      .with_label(span),
+  );
+}
+
+#[cold]
+pub fn vue3_only_syntax(errors: &mut Vec<OxcDiagnostic>, syntax: &str, span: Span) {
+  errors
+    .push(OxcDiagnostic::error(format!("{syntax} is not supported in Vue 2.")).with_label(span));
+}
+
+#[cold]
+pub fn unknown_css_module_class(
+  errors: &mut Vec<OxcDiagnostic>,
+  module: &str,
+  class: &str,
+  span: Span,
+) {
+  errors.push(
+    OxcDiagnostic::error(format!("Class `{class}` is not defined in the CSS module `{module}`."))
+      .with_label(span),
+  );
+}
+
+#[cold]
+pub fn unused_css_module_class(
+  errors: &mut Vec<OxcDiagnostic>,
+  module: &str,
+  class: &str,
+  span: Span,
+) {
+  errors.push(
+    OxcDiagnostic::warn(format!("Class `{class}` of the CSS module `{module}` is never used."))
+      .with_label(span),
+  );
+}
+
+#[cold]
+pub fn invalid_json(errors: &mut Vec<OxcDiagnostic>, lang: &str, message: &str, span: Span) {
+  errors.push(OxcDiagnostic::error(format!("Invalid {lang}: {message}")).with_label(span));
+}
+
+#[cold]
+pub fn invalid_yaml(errors: &mut Vec<OxcDiagnostic>, message: &str, span: Span) {
+  errors.push(OxcDiagnostic::error(format!("Invalid YAML: {message}")).with_label(span));
+}
@@ -0,0 +1,329 @@
+//! Blocks whose content lives in another file, like `<script src="./foo.ts">`.
+//!
+//! The content is read by a [`SourceResolver`] and parsed in its own span space, placed after
+//! the SFC and the files read before it, so the spans of its nodes never overlap those of the SFC.
+//! Its diagnostics are reported with the [`FileId`] of the file instead of along the SFC errors.
+
+use std::{
+  fmt, fs, io,
+  path::{Path, PathBuf},
+};
+
+use oxc_allocator::{TakeIn, Vec as ArenaVec};
+use oxc_ast::ast::{JSXChild, Statement};
+use oxc_ast_visit::VisitMut;
+use oxc_diagnostics::{LabeledSpan, OxcDiagnostic};
+use oxc_span::{SourceType, Span};
+use oxc_str::Str;
+use oxc_syntax::module_record::{
+  ExportEntry, ExportExportName, ExportImportName, ExportLocalName, NameSpan, RequestedModule,
+};
+use rustc_hash::FxHashMap;
+use vue_compiler_core::parser::{AstNode, ElemProp, Element};
+
+use crate::parser::{
+  ParseConfig, ParserImpl, error,
+  parse::{SourceLocatonSpan, SpanShifter, attr_value_span, scan_sfc},
+};
+
+/// Reads the content of the `src` of a block.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use oxc_allocator::Allocator;
+/// use vue_oxlint_jsx::{MemorySourceResolver, VueJsxParser};
+///
+/// let allocator = Allocator::default();
+/// let source = r#"<script src="./foo.js"></script><template><Foo /></template>"#;
+/// let resolver = MemorySourceResolver::new().with_file("./foo.js", "import Foo from './Foo.vue'");
+///
+/// let ret = VueJsxParser::new(&allocator, source).with_source_resolver(Arc::new(resolver)).parse();
+/// assert_eq!(ret.external_sources.len(), 1);
+/// assert!(ret.module_record.requested_modules.contains_key("./foo.js"));
+/// ```
+pub trait SourceResolver: Send + Sync {
+  /// Read the file of `src`, as written in the attribute.
+  ///
+  /// # Errors
+  ///
+  /// If the file can't be read, which is reported at the `src` attribute.
+  fn resolve(&self, src: &str) -> io::Result<String>;
+}
+
+impl fmt::Debug for dyn SourceResolver {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("SourceResolver")
+  }
+}
+
+/// Resolves `src` relative to a directory, usually the one of the SFC.
+#[derive(Debug)]
+pub struct FsSourceResolver {
+  base_dir: PathBuf,
+}
+
+impl FsSourceResolver {
+  #[must_use]
+  pub fn new(base_dir: impl AsRef<Path>) -> Self {
+    Self { base_dir: base_dir.as_ref().to_path_buf() }
+  }
+}
+
+impl SourceResolver for FsSourceResolver {
+  fn resolve(&self, src: &str) -> io::Result<String> {
+    fs::read_to_string(self.base_dir.join(src))
+  }
+}
+
+/// Resolves `src` from a fixed set of files, for tests and editors with unsaved buffers.
+#[derive(Debug, Default)]
+pub struct MemorySourceResolver {
+  files: FxHashMap<String, String>,
+}
+
+impl MemorySourceResolver {
+  #[must_use]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  #[must_use]
+  pub fn with_file(mut self, src: impl Into<String>, content: impl Into<String>) -> Self {
+    self.files.insert(src.into(), content.into());
+    self
+  }
+}
+
+impl SourceResolver for MemorySourceResolver {
+  fn resolve(&self, src: &str) -> io::Result<String> {
+    self.files.get(src).cloned().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+  }
+}
+
+/// Identifies the file a span or a diagnostic belongs to. The SFC itself is [`FileId::SFC`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId(pub u32);
+
+impl FileId {
+  pub const SFC: Self = Self(0);
+}
+
+/// The resolved content of a block's `src`.
+#[derive(Debug)]
+pub struct ExternalSource<'a> {
+  pub file_id: FileId,
+  /// The tag name of the block, e.g. `script`.
+  pub tag: &'a str,
+  /// The `src` attribute as written.
+  pub src: &'a str,
+  pub source_text: &'a str,
+  /// Where the span space of the file starts: the nodes from it are at `span_offset` plus their
+  /// offset in [`Self::source_text`].
+  pub span_offset: u32,
+}
+
+impl ExternalSource<'_> {
+  /// The span of a node from this file in [`Self::source_text`], or [`None`] if the node is not
+  /// from this file.
+  #[must_use]
+  pub fn local_span(&self, span: Span) -> Option<Span> {
+    let end = self.span_offset + self.source_text.len() as u32;
+    (span.start >= self.span_offset && span.end <= end).then(|| span.move_left(self.span_offset))
+  }
+}
+
+/// A diagnostic of an external file, with spans in the span space of that file.
+#[derive(Debug)]
+pub struct FileDiagnostic {
+  pub file_id: FileId,
+  pub diagnostic: OxcDiagnostic,
+}
+
+/// `<template>` wrapping an external template, so it is scanned like one of an SFC.
+const TEMPLATE_OPEN: &str = "<template>";
+
+impl<'a> ParserImpl<'a> {
+  /// The `src` attribute of a block, with the span of its value.
+  pub fn block_src(node: &Element<'a>) -> Option<(&'a str, Span)> {
+    node.properties.iter().find_map(|prop| match prop {
+      ElemProp::Attr(attr) if attr.name == "src" => {
+        attr.value.as_ref().map(|value| (value.content.raw, attr_value_span(value)))
+      }
+      _ => None,
+    })
+  }
+
+  /// Record the `src` of a block as a dependency of the module, without reading it. Returns
+  /// [`None`] if no resolver is configured.
+  pub fn record_src(&mut self, node: &Element<'a>) -> Option<(&'a str, Span)> {
+    let (src, span) = Self::block_src(node)?;
+    self.config.source_resolver.as_ref()?;
+
+    let ast = self.ast;
+    self.module_record.requested_modules.entry(Str::from(src)).or_insert_with(|| ast.vec()).push(
+      RequestedModule {
+        statement_span: node.location.span(),
+        span,
+        is_type: false,
+        is_import: true,
+      },
+    );
+    Some((src, span))
+  }
+
+  /// Read the `src` of a block and record it as a dependency of the module. Returns [`None`]
+  /// if no resolver is configured, so the block stays empty, or the file can't be read.
+  pub fn resolve_src(&mut self, node: &Element<'a>) -> Option<&ExternalSource<'a>> {
+    let (src, span) = self.record_src(node)?;
+    let resolver = self.config.source_resolver.clone()?;
+
+    match resolver.resolve(src) {
+      Ok(content) => {
+        let file_id = FileId(self.external_sources.len() as u32 + 1);
+        // +1 so an empty span at the end of the previous file isn't at the start of this one
+        let span_offset =
+          self.external_sources.last().map_or(self.source_text.len() as u32, |last| {
+            last.span_offset + last.source_text.len() as u32
+          }) + 1;
+        let source_text = self.allocator.alloc_str(&content);
+        self.external_sources.push(ExternalSource {
+          file_id,
+          tag: node.tag_name,
+          src,
+          source_text,
+          span_offset,
+        });
+        self.external_sources.last()
+      }
+      Err(err) => {
+        error::unresolved_src(&mut self.errors, src, &err, span);
+        None
+      }
+    }
+  }
+
+  /// Parse the file of `<script src>` as the plain `<script>`, exporting its default export.
+  pub fn parse_external_script(&mut self, node: &Element<'a>, source_type: SourceType) {
+    let Some(&ExternalSource { file_id, src, source_text, span_offset, .. }) =
+      self.resolve_src(node)
+    else {
+      return;
+    };
+    let Some((_, span)) = Self::block_src(node) else { return };
+    self.script_set = true;
+
+    // `export { default } from './foo.ts'`
+    self.module_record.indirect_export_entries.push(ExportEntry {
+      statement_span: node.location.span(),
+      span,
+      module_request: Some(NameSpan::new(Str::from(src), span)),
+      import_name: ExportImportName::Name(NameSpan::new(Str::from("default"), span)),
+      export_name: ExportExportName::Default(span),
+      local_name: ExportLocalName::Null,
+      is_type: false,
+    });
+
+    let ret = oxc_parser::Parser::new(self.allocator, source_text, source_type)
+      .with_options(self.options)
+      .parse();
+    self
+      .external_errors
+      .extend(ret.errors.into_iter().map(|diagnostic| FileDiagnostic { file_id, diagnostic }));
+    if ret.panicked {
+      return;
+    }
+
+    let mut program = ret.program;
+    SpanShifter(span_offset).visit_program(&mut program);
+    let mut body: ArenaVec<'a, Statement<'a>> = program.body.take_in(self.ast);
+    self.collect_script_bindings(&body);
+    self.global.directives.append(&mut program.directives);
+    self.global.statements.append(&mut body);
+  }
+
+  /// Parse the file of `<template src>` into the children of the template.
+  pub fn parse_external_template(
+    &mut self,
+    node: &Element<'a>,
+  ) -> Option<ArenaVec<'a, JSXChild<'a>>> {
+    let &ExternalSource { file_id, source_text: content, span_offset, .. } =
+      self.resolve_src(node)?;
+    let source_text = self.allocator.alloc_str(&format!("{TEMPLATE_OPEN}{content}</template>"));
+
+    let config = ParseConfig { source_resolver: None, ..self.config.clone() };
+    let mut parser = ParserImpl::new(self.allocator, source_text, self.options, config);
+    parser.script_bindings.clone_from(&self.script_bindings);
+
+    let mut scan_errors = vec![];
+    let template = scan_sfc(parser.source_text, &parser.config.delimiters, &mut scan_errors)
+      .and_then(|nodes| {
+        nodes.into_iter().find_map(|node| match node {
+          AstNode::Element(node) if node.tag_name == "template" => Some(node),
+          _ => None,
+        })
+      })
+      .map(|template| parser.parse_element(template, None).0);
+
+    let shift = TEMPLATE_OPEN.len() as u32;
+    self.external_errors.extend(scan_errors.into_iter().chain(parser.errors).map(
+      |mut diagnostic| {
+        if let Some(labels) = &mut diagnostic.labels {
+          for label in labels.iter_mut() {
+            let offset = (label.offset() as u32).saturating_sub(shift);
+            *label =
+              LabeledSpan::new(label.label().map(str::to_string), offset as usize, label.len());
+          }
+        }
+        FileDiagnostic { file_id, diagnostic }
+      },
+    ));
+
+    let Some(JSXChild::Element(mut template)) = template else { return None };
+    let mut children = template.children.take_in(self.ast);
+    for child in &mut children {
+      SpanShifter(span_offset - shift).visit_jsx_child(child);
+    }
+    Some(children)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use crate::{MemorySourceResolver, parser::ParseConfig, test::run_test_with_config, test_ast};
+
+  fn config() -> ParseConfig {
+    let resolver = MemorySourceResolver::new()
+      .with_file(
+        "./component.ts",
+        "import Card from './Card.vue'\n\nexport default defineComponent({\n  components: { Card },\n  data: () => ({ title: 'Hi' }),\n})\n",
+      )
+      .with_file("./template.html", "\n  <Card :title=\"title\" />\n  <p>{{ title }}</p>\n")
+      .with_file("./style.css", ".card { color: red; }\n")
+      .with_file("./broken.ts", "export default {\n")
+      .with_file("./broken.html", "\n  <p v-if=\"\">{{ title }}</p>\n");
+    ParseConfig { source_resolver: Some(Arc::new(resolver)), ..Default::default() }
+  }
+
+  test_ast!(external_vue, "external/external.vue", config: super::config());
+  test_ast!(external_errors_vue, "external/errors.vue", true, false, config: super::config());
+
+  #[test]
+  fn external_sources() {
+    for path in ["external/external.vue", "external/errors.vue", "external/unquoted.vue"] {
+      run_test_with_config(path, "external", config(), |ret| {
+        format!(
+          "External Sources: {:#?}\n\nExternal Errors: {:#?}\n\nRequested Modules: {:#?}\n\nIndirect Exports: {:#?}",
+          ret.external_sources,
+          ret.external_errors,
+          ret.module_record.requested_modules,
+          ret.module_record.indirect_export_entries,
+        )
+      });
+    }
+  }
+}
@@ -0,0 +1,448 @@
+//! Templates written as strings in JS/TS files, like `defineComponent({ template: '<p />' })`.
+//!
+//! Each template is parsed in the span space of its host file and spliced into the host
+//! [`Program`] in place of the string, as an arrow function receiving the members of the
+//! component (`template: ({ msg }) => <><p>{msg}</p></>`), so its references resolve to them.
+
+use oxc_allocator::{Allocator, TakeIn, Vec as ArenaVec};
+use oxc_ast::{
+  AstBuilder, Comment, NONE,
+  ast::{
+    Argument, CallExpression, ExportDefaultDeclaration, ExportDefaultDeclarationKind, Expression,
+    FormalParameterKind, JSXChild, NewExpression, ObjectExpression, ObjectPropertyKind, Program,
+    VariableDeclarator,
+  },
+};
+use oxc_ast_visit::{VisitMut, walk_mut};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_parser::ParseOptions;
+use oxc_span::{SPAN, SourceType, Span};
+use oxc_syntax::identifier::is_identifier_name;
+use rustc_hash::FxHashSet;
+use vue_compiler_core::parser::AstNode;
+
+use crate::parser::{
+  ComponentDefinition, ComponentMemberKind, CustomElements, Delimiters, OptionsApiComponent,
+  ParseConfig, ParserImpl, VueVersion, options_api::key_name, parse::scan_sfc,
+  script::collect_bindings,
+};
+
+/// `<template>` wrapping a template string, written over the `template: '` before it.
+const TEMPLATE_OPEN: &str = "<template>";
+
+/// Finds the templates of the components of a JS/TS file and transforms them into JSX.
+///
+/// The components are the options objects of `defineComponent({})`, `Vue.extend({})`,
+/// `Vue.component('name', {})`, `app.component('name', {})` (`app` being named so or created by
+/// `createApp()`), `new Vue({})`, `createApp({})` and
+/// `export default {}`, and of their `components` option. Only string literals and template
+/// literals without expressions are templates, read as written, so escape sequences are kept.
+///
+/// # Examples
+///
+/// ```
+/// use oxc_allocator::Allocator;
+/// use oxc_span::SourceType;
+/// use vue_oxlint_jsx::InlineTemplates;
+///
+/// let allocator = Allocator::default();
+/// let source = "defineComponent({ data: () => ({ msg: 'hi' }), template: `<p>{{ msg }}</p>` })";
+/// let mut program = oxc_parser::Parser::new(&allocator, source, SourceType::mjs()).parse().program;
+///
+/// let ret = InlineTemplates::new(&allocator, source).transform(&mut program);
+/// assert_eq!(ret.templates.len(), 1);
+/// assert!(ret.templates[0].component.member("msg").is_some());
+/// ```
+pub struct InlineTemplates<'a> {
+  allocator: &'a Allocator,
+  source_text: &'a str,
+  vue_version: VueVersion,
+  custom_elements: CustomElements,
+  delimiters: Delimiters,
+}
+
+/// The return value of [`InlineTemplates::transform`].
+#[non_exhaustive]
+pub struct InlineTemplatesReturn<'a> {
+  pub templates: Vec<InlineTemplate<'a>>,
+  /// Diagnostics of the templates, with spans in the host file.
+  pub errors: Vec<OxcDiagnostic>,
+}
+
+/// A template transformed into JSX.
+#[derive(Debug)]
+pub struct InlineTemplate<'a> {
+  /// Span of the template, without the quotes.
+  pub span: Span,
+  /// The component whose `template` option it is.
+  pub component: OptionsApiComponent<'a>,
+}
+
+impl<'a> InlineTemplates<'a> {
+  /// Creates a new [`InlineTemplates`] for the JS/TS file `source_text`, which the program to
+  /// transform was parsed from.
+  #[must_use]
+  pub fn new(allocator: &'a Allocator, source_text: &'a str) -> Self {
+    Self {
+      allocator,
+      source_text,
+      vue_version: VueVersion::default(),
+      custom_elements: CustomElements::new(),
+      delimiters: Delimiters::default(),
+    }
+  }
+
+  /// Selects the Vue version whose template semantics the JSX follows. Defaults to
+  /// [`VueVersion::V3`].
+  #[must_use]
+  pub const fn with_vue_version(mut self, vue_version: VueVersion) -> Self {
+    self.vue_version = vue_version;
+    self
+  }
+
+  /// Sets the tags which are custom elements (web components), like Vue's
+  /// `compilerOptions.isCustomElement`.
+  #[must_use]
+  pub fn with_custom_elements(mut self, custom_elements: CustomElements) -> Self {
+    self.custom_elements = custom_elements;
+    self
+  }
+
+  /// Sets the interpolation delimiters. A component's own `delimiters` option takes precedence.
+  #[must_use]
+  pub fn with_delimiters(mut self, delimiters: Delimiters) -> Self {
+    self.delimiters = delimiters;
+    self
+  }
+
+  /// Replace the templates of the components in `program` by their JSX.
+  pub fn transform(self, program: &mut Program<'a>) -> InlineTemplatesReturn<'a> {
+    let mut script_bindings = FxHashSet::default();
+    collect_bindings(&program.body, &mut script_bindings);
+
+    let ast = AstBuilder::new(self.allocator);
+    let mut transformer = Transformer {
+      options: self,
+      ast,
+      source_type: program.source_type,
+      script_bindings,
+      app_names: FxHashSet::default(),
+      templates: vec![],
+      errors: vec![],
+      comments: vec![],
+    };
+    transformer.visit_program(program);
+
+    if !transformer.templates.is_empty() {
+      program.source_type = program.source_type.with_jsx(true);
+      program.comments.extend(transformer.comments);
+      program.comments.sort_by_key(|comment| comment.span.start);
+    }
+    InlineTemplatesReturn { templates: transformer.templates, errors: transformer.errors }
+  }
+}
+
+struct Transformer<'a> {
+  options: InlineTemplates<'a>,
+  ast: AstBuilder<'a>,
+  source_type: SourceType,
+  script_bindings: FxHashSet<&'a str>,
+  /// The bindings of `createApp()`, whose `.component()` registers a component.
+  app_names: FxHashSet<&'a str>,
+  templates: Vec<InlineTemplate<'a>>,
+  errors: Vec<OxcDiagnostic>,
+  comments: Vec<Comment>,
+}
+
+impl<'a> VisitMut<'a> for Transformer<'a> {
+  fn visit_call_expression(&mut self, it: &mut CallExpression<'a>) {
+    let definition = if it.callee.is_specific_id("defineComponent") {
+      Some((ComponentDefinition::DefineComponent, 0))
+    } else if it.callee.is_specific_member_access("Vue", "extend") {
+      Some((ComponentDefinition::VueExtend, 0))
+    } else if it.callee.is_specific_id("createApp") {
+      Some((ComponentDefinition::RootInstance, 0))
+    } else if let Expression::StaticMemberExpression(member) = &it.callee
+      && member.property.name == "component"
+      && self.is_vue_app(&member.object)
+    {
+      // The options follow the name of `Vue.component('name', {})`
+      Some((ComponentDefinition::GlobalComponent, 1))
+    } else {
+      None
+    };
+    if let Some((definition, index)) = definition
+      && let Some(Argument::ObjectExpression(object)) = it.arguments.get_mut(index)
+    {
+      self.transform_component(definition, object);
+    }
+    walk_mut::walk_call_expression(self, it);
+  }
+
+  fn visit_variable_declarator(&mut self, it: &mut VariableDeclarator<'a>) {
+    // `const root = createApp({})`
+    if let Some(Expression::CallExpression(call)) = &it.init
+      && call.callee.is_specific_id("createApp")
+      && let Some(name) = it.id.get_identifier_name()
+    {
+      self.app_names.insert(name.into());
+    }
+    walk_mut::walk_variable_declarator(self, it);
+  }
+
+  fn visit_new_expression(&mut self, it: &mut NewExpression<'a>) {
+    if it.callee.is_specific_id("Vue")
+      && let Some(Argument::ObjectExpression(object)) = it.arguments.first_mut()
+    {
+      self.transform_component(ComponentDefinition::RootInstance, object);
+    }
+    walk_mut::walk_new_expression(self, it);
+  }
+
+  fn visit_export_default_declaration(&mut self, it: &mut ExportDefaultDeclaration<'a>) {
+    if let ExportDefaultDeclarationKind::ObjectExpression(object) = &mut it.declaration {
+      self.transform_component(ComponentDefinition::ExportDefault, object);
+    }
+    walk_mut::walk_export_default_declaration(self, it);
+  }
+}
+
+impl<'a> Transformer<'a> {
+  /// Whether `.component()` is called on `Vue` or an app: `app`, a binding of `createApp()` or
+  /// the result of `createApp()`, including chained `.component()` calls.
+  fn is_vue_app(&self, object: &Expression<'a>) -> bool {
+    match object.get_inner_expression() {
+      Expression::Identifier(ident) => {
+        matches!(ident.name.as_str(), "Vue" | "app") || self.app_names.contains(ident.name.as_str())
+      }
+      Expression::CallExpression(call) => {
+        call.callee.is_specific_id("createApp")
+          || matches!(&call.callee, Expression::StaticMemberExpression(member)
+            if member.property.name == "component" && self.is_vue_app(&member.object))
+      }
+      _ => false,
+    }
+  }
+
+  fn transform_component(
+    &mut self,
+    definition: ComponentDefinition,
+    object: &mut ObjectExpression<'a>,
+  ) {
+    let mut template = None;
+    for property in &mut object.properties {
+      let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+      match key_name(&property.key) {
+        Some("template") => template = template_span(&property.value),
+        Some("components") => {
+          if let Expression::ObjectExpression(components) = &mut property.value {
+            for component in &mut components.properties {
+              if let ObjectPropertyKind::ObjectProperty(component) = component
+                && let Expression::ObjectExpression(object) = &mut component.value
+              {
+                self.transform_component(ComponentDefinition::LocalComponent, object);
+              }
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+    let Some(span) = template else { return };
+
+    let component = OptionsApiComponent::from_object(self.options.allocator, definition, object);
+    let (Some(component), Some(children)) = self.parse_template(span, component) else { return };
+    let value = self.template_function(&component, span, children);
+    for property in &mut object.properties {
+      if let ObjectPropertyKind::ObjectProperty(property) = property
+        && key_name(&property.key) == Some("template")
+      {
+        property.value = value;
+        break;
+      }
+    }
+    self.templates.push(InlineTemplate { span, component });
+  }
+
+  /// Parse the template at `span` of the host file. The component is lent to the parser, which
+  /// resolves its local directives, and given back along the children, [`None`] if the template
+  /// can't be parsed.
+  fn parse_template(
+    &mut self,
+    span: Span,
+    component: OptionsApiComponent<'a>,
+  ) -> (Option<OptionsApiComponent<'a>>, Option<ArenaVec<'a, JSXChild<'a>>>) {
+    // Spaces up to the template, keeping its spans those of the host file
+    let start = span.start as usize;
+    let mut source_text = " ".repeat(start - TEMPLATE_OPEN.len());
+    source_text.push_str(TEMPLATE_OPEN);
+    source_text.push_str(span.source_text(self.options.source_text));
+    source_text.push_str("</template>");
+    let source_text = self.options.allocator.alloc_str(&source_text);
+
+    let delimiters = component.delimiters.map_or_else(
+      || self.options.delimiters.clone(),
+      |(open, close)| Delimiters::new(open, close),
+    );
+    let config = ParseConfig {
+      vue_version: self.options.vue_version,
+      custom_elements: self.options.custom_elements.clone(),
+      delimiters,
+      ..ParseConfig::default()
+    };
+    let mut parser =
+      ParserImpl::new(self.options.allocator, source_text, ParseOptions::default(), config);
+    parser.source_type = self.source_type;
+    parser.script_bindings.clone_from(&self.script_bindings);
+
+    let template = scan_sfc(parser.source_text, &parser.config.delimiters, &mut self.errors)
+      .and_then(|nodes| {
+        nodes.into_iter().find_map(|node| match node {
+          AstNode::Element(node) if node.tag_name == "template" => Some(node),
+          _ => None,
+        })
+      });
+
+    parser.options_api = Some(component);
+    let template = template.map(|template| parser.parse_element(template, None).0);
+    self.errors.append(&mut parser.errors);
+    self.comments.extend(parser.comments.iter().copied());
+
+    let children = match template {
+      Some(JSXChild::Element(mut template)) => Some(template.children.take_in(self.ast)),
+      _ => None,
+    };
+    (parser.options_api.take(), children)
+  }
+
+  /// `({ msg, count }) => <>children</>`, with a parameter for each instance member.
+  fn template_function(
+    &self,
+    component: &OptionsApiComponent<'a>,
+    span: Span,
+    children: ArenaVec<'a, JSXChild<'a>>,
+  ) -> Expression<'a> {
+    let ast = self.ast;
+    let mut names = FxHashSet::default();
+    let properties = ast.vec_from_iter(
+      component
+        .members
+        .iter()
+        .filter(|member| {
+          !matches!(member.kind, ComponentMemberKind::Emit | ComponentMemberKind::Watch)
+            && is_identifier_name(member.name)
+            && names.insert(member.name)
+        })
+        .map(|member| {
+          ast.binding_property(
+            SPAN,
+            ast.property_key_static_identifier(SPAN, member.name),
+            ast.binding_pattern_binding_identifier(SPAN, member.name),
+            true,
+            false,
+          )
+        }),
+    );
+    let params = ast.formal_parameters(
+      SPAN,
+      FormalParameterKind::ArrowFormalParameters,
+      ast.vec1(ast.formal_parameter(
+        SPAN,
+        ast.vec(),
+        ast.binding_pattern_object_pattern(SPAN, properties, NONE),
+        NONE,
+        NONE,
+        false,
+        None,
+        false,
+        false,
+      )),
+      NONE,
+    );
+    let body = ast.function_body(
+      SPAN,
+      ast.vec(),
+      ast.vec1(ast.statement_expression(
+        SPAN,
+        ast.expression_jsx_fragment(
+          span,
+          ast.jsx_opening_fragment(SPAN),
+          children,
+          ast.jsx_closing_fragment(SPAN),
+        ),
+      )),
+    );
+    ast.expression_arrow_function(span.expand(1), true, false, NONE, params, NONE, body)
+  }
+}
+
+/// The span of a template string without its quotes. Templates starting with `#` are the
+/// selector of an element holding the template, and templates with expressions are dynamic.
+fn template_span(value: &Expression<'_>) -> Option<Span> {
+  let (span, template) = match value {
+    Expression::StringLiteral(lit) => (lit.span, lit.value.as_str()),
+    Expression::TemplateLiteral(lit) if lit.expressions.is_empty() => {
+      (lit.span, lit.quasis.first()?.value.raw.as_str())
+    }
+    _ => return None,
+  };
+  (!template.starts_with('#')).then(|| span.shrink(1))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{fmt::Write, path::Path};
+
+  use oxc_allocator::Allocator;
+  use oxc_span::SourceType;
+
+  use crate::{
+    InlineTemplates,
+    test::{format_node_locations, format_program_codegen, read_file, snapshot_name},
+  };
+
+  fn test_inline_template(file_path: &str) {
+    let allocator = Allocator::default();
+    let source_text = read_file(file_path);
+    let source_type = SourceType::from_path(Path::new(file_path)).unwrap();
+    let mut program =
+      oxc_parser::Parser::new(&allocator, &source_text, source_type).parse().program;
+
+    let ret = InlineTemplates::new(&allocator, &source_text).transform(&mut program);
+
+    let codegen = format_program_codegen(&program);
+
+    let mut templates = String::new();
+    for template in &ret.templates {
+      let members: Vec<_> = template.component.members.iter().map(|member| member.name).collect();
+      let _ = writeln!(
+        templates,
+        "{:?} {:?}: {members:?}",
+        template.component.definition,
+        template.span.source_text(&source_text).trim(),
+      );
+    }
+    let result = format!(
+      "=============== Templates ===============\n\n{templates}\n===============  Error  ===============\n\n{:#?}\n\n=============== Codegen ===============\n\n{codegen}\n\n===============  Spans  ===============\n\n{}",
+      ret.errors,
+      format_node_locations(&program, &source_text, &[]),
+    );
+
+    let mut settings = insta::Settings::clone_current();
+    settings.set_snapshot_path("../test/snapshots/inline_template");
+    settings.set_prepend_module_to_snapshot(false);
+    settings.bind(|| {
+      insta::assert_snapshot!(snapshot_name(file_path), result);
+    });
+  }
+
+  #[test]
+  fn components() {
+    test_inline_template("inline_template/components.js");
+  }
+
+  #[test]
+  fn errors() {
+    test_inline_template("inline_template/errors.ts");
+  }
+}
@@ -1,16 +1,29 @@
+use std::sync::Arc;
+
 use oxc_allocator::{Allocator, Dummy};
 use oxc_ast::ast::Program;
-use oxc_diagnostics::OxcDiagnostic;
+use oxc_ast_visit::VisitMut;
+use oxc_diagnostics::{LabeledSpan, OxcDiagnostic};
 use oxc_parser::ParseOptions;
-use oxc_span::Span;
+use oxc_span::{SourceType, Span};
 use oxc_syntax::module_record::ModuleRecord;
+use vue_oxlint_parser::SfcDescriptor;
 
-use crate::parser::{ParseConfig, ParserImpl, ParserImplReturn};
+use crate::parser::{
+  CustomBlockHandlers, CustomElements, Delimiters, ExternalSource, FileDiagnostic,
+  OptionsApiComponent, ParseConfig, ParserImpl, ParserImplReturn, SourceResolver, VirtualDocument,
+  VueVersion, error, modules::MoveRight, parse::SpanShifter,
+};
 
 pub struct VueJsxParser<'a> {
   allocator: &'a Allocator,
   source_text: &'a str,
   options: ParseOptions,
+  vue_version: VueVersion,
+  custom_elements: CustomElements,
+  delimiters: Delimiters,
+  custom_block_handlers: CustomBlockHandlers,
+  source_resolver: Option<Arc<dyn SourceResolver>>,
 }
 
 /// The return value of [`VueJsxParser::parse`].
@@ -24,6 +37,46 @@ pub struct VueJsxParserReturn<'a> {
   pub module_record: ModuleRecord<'a>,
   pub errors: Vec<OxcDiagnostic>,
   pub irregular_whitespaces: Box<[Span]>,
+  /// The Options API component model (`export default {}`, `defineComponent({})` or
+  /// `Vue.extend({})`) declared in the plain `<script>` block, if any.
+  pub options_api: Option<OptionsApiComponent<'a>>,
+  /// The files read for `<script src>` and `<template src>`, whose nodes are spanned in the span
+  /// space of each file.
+  pub external_sources: Vec<ExternalSource<'a>>,
+  /// Diagnostics of [`VueJsxParserReturn::external_sources`], with spans in their files.
+  pub external_errors: Vec<FileDiagnostic>,
+  /// The `<style>` and custom blocks as virtual documents, in source order.
+  pub virtual_documents: Vec<VirtualDocument>,
+  pub panicked: bool,
+}
+
+/// The return value of [`VueJsxParser::parse_scripts`].
+#[non_exhaustive]
+pub struct VueScriptsReturn<'a> {
+  /// The `<script>` and `<script setup>` blocks, in source order.
+  pub scripts: Vec<VueScriptSection<'a>>,
+  /// Diagnostics of the SFC structure, like a duplicated `<script setup>` or an unsupported
+  /// `lang`. Syntax errors are reported by each section.
+  pub errors: Vec<OxcDiagnostic>,
+}
+
+/// A `<script>` or `<script setup>` block parsed on its own.
+///
+/// The spans of [`Self::program`], [`Self::module_record`] and [`Self::errors`] are offsets of
+/// the SFC, which is the source text of the program.
+#[non_exhaustive]
+pub struct VueScriptSection<'a> {
+  /// Whether the block is `<script setup>`.
+  pub setup: bool,
+  /// The offset of the content of the block in the SFC.
+  pub start: u32,
+  /// The content of the block.
+  pub source_text: &'a str,
+  /// The source type of the `lang` attribute, JavaScript by default.
+  pub source_type: SourceType,
+  pub program: Program<'a>,
+  pub module_record: ModuleRecord<'a>,
+  pub errors: Vec<OxcDiagnostic>,
   pub panicked: bool,
 }
 
@@ -49,7 +102,16 @@ impl<'a> VueJsxParser<'a> {
   /// assert!(!ret.panicked);
   /// ```
   pub fn new(allocator: &'a Allocator, source_text: &'a str) -> Self {
-    Self { allocator, source_text, options: ParseOptions::default() }
+    Self {
+      allocator,
+      source_text,
+      options: ParseOptions::default(),
+      vue_version: VueVersion::default(),
+      custom_elements: CustomElements::new(),
+      delimiters: Delimiters::default(),
+      custom_block_handlers: CustomBlockHandlers::new(),
+      source_resolver: None,
+    }
   }
 
   /// Overrides the [`ParseOptions`] passed to the underlying `oxc_parser`.
@@ -73,6 +135,120 @@ impl<'a> VueJsxParser<'a> {
     self.options = options;
     self
   }
+
+  /// Selects the Vue version whose template semantics (e.g. `v-if` / `v-for` precedence) the
+  /// produced AST follows. Defaults to [`VueVersion::V3`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use oxc_allocator::Allocator;
+  /// use vue_oxlint_jsx::{VueJsxParser, VueVersion};
+  ///
+  /// let allocator = Allocator::default();
+  /// let source = r#"<template><li v-for="u in users" v-if="u.active" /></template>"#;
+  ///
+  /// let ret = VueJsxParser::new(&allocator, source).with_vue_version(VueVersion::V2).parse();
+  /// assert!(ret.errors.is_empty());
+  /// ```
+  #[must_use]
+  pub const fn with_vue_version(mut self, vue_version: VueVersion) -> Self {
+    self.vue_version = vue_version;
+    self
+  }
+
+  /// Sets the tags which are custom elements (web components), like Vue's
+  /// `compilerOptions.isCustomElement`. They are kept as intrinsic elements.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use oxc_allocator::Allocator;
+  /// use vue_oxlint_jsx::{CustomElements, VueJsxParser};
+  ///
+  /// let allocator = Allocator::default();
+  /// let source = r#"<template><sl-button>Click</sl-button></template>"#;
+  ///
+  /// let ret = VueJsxParser::new(&allocator, source)
+  ///   .with_custom_elements(CustomElements::new().with_prefix("sl-"))
+  ///   .parse();
+  /// assert!(!ret.panicked);
+  /// ```
+  #[must_use]
+  pub fn with_custom_elements(mut self, custom_elements: CustomElements) -> Self {
+    self.custom_elements = custom_elements;
+    self
+  }
+
+  /// Sets the interpolation delimiters, like Vue's `compilerOptions.delimiters`. Defaults to `{{`
+  /// and `}}`. A component's own `delimiters` option takes precedence.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use oxc_allocator::Allocator;
+  /// use vue_oxlint_jsx::{Delimiters, VueJsxParser};
+  ///
+  /// let allocator = Allocator::default();
+  /// let source = r#"<template><div>${ msg }</div></template>"#;
+  ///
+  /// let ret =
+  ///   VueJsxParser::new(&allocator, source).with_delimiters(Delimiters::new("${", "}")).parse();
+  /// assert!(ret.errors.is_empty());
+  /// ```
+  #[must_use]
+  pub fn with_delimiters(mut self, delimiters: Delimiters) -> Self {
+    self.delimiters = delimiters;
+    self
+  }
+
+  /// Sets the handlers checking custom blocks like `<i18n>` or `<route>`, by tag name and `lang`.
+  /// No custom block is checked by default.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use oxc_allocator::Allocator;
+  /// use vue_oxlint_jsx::{CustomBlockHandlers, VueJsxParser};
+  ///
+  /// let allocator = Allocator::default();
+  /// let source = r#"<i18n>{ "en": { "hello": "Hello" }, }</i18n>"#;
+  ///
+  /// let ret = VueJsxParser::new(&allocator, source)
+  ///   .with_custom_block_handlers(CustomBlockHandlers::builtin())
+  ///   .parse();
+  /// assert_eq!(ret.errors[0].message, "Invalid JSON: Key must be a string.");
+  /// ```
+  #[must_use]
+  pub fn with_custom_block_handlers(mut self, custom_block_handlers: CustomBlockHandlers) -> Self {
+    self.custom_block_handlers = custom_block_handlers;
+    self
+  }
+
+  /// Sets the [`SourceResolver`] reading the `src` of `<script src>` and `<template src>`. The
+  /// `src` of `<style src>` is only recorded. Without one, those blocks are left empty.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::sync::Arc;
+  ///
+  /// use oxc_allocator::Allocator;
+  /// use vue_oxlint_jsx::{MemorySourceResolver, VueJsxParser};
+  ///
+  /// let allocator = Allocator::default();
+  /// let source = r#"<template src="./missing.html"></template>"#;
+  ///
+  /// let ret = VueJsxParser::new(&allocator, source)
+  ///   .with_source_resolver(Arc::new(MemorySourceResolver::new()))
+  ///   .parse();
+  /// assert!(ret.errors[0].message.starts_with("Cannot resolve `./missing.html`"));
+  /// ```
+  #[must_use]
+  pub fn with_source_resolver(mut self, source_resolver: Arc<dyn SourceResolver>) -> Self {
+    self.source_resolver = Some(source_resolver);
+    self
+  }
 }
 
 impl<'a> VueJsxParser<'a> {
@@ -99,9 +275,31 @@ impl<'a> VueJsxParser<'a> {
   /// ```
   #[must_use]
   pub fn parse(self) -> VueJsxParserReturn<'a> {
-    let ParserImplReturn { program, errors, fatal, module_record, irregular_whitespaces, .. } =
-      ParserImpl::new(self.allocator, self.source_text, self.options, ParseConfig::default())
-        .parse();
+    let ParserImplReturn {
+      program,
+      errors,
+      fatal,
+      module_record,
+      irregular_whitespaces,
+      options_api,
+      external_sources,
+      external_errors,
+      virtual_documents,
+      ..
+    } = ParserImpl::new(
+      self.allocator,
+      self.source_text,
+      self.options,
+      ParseConfig {
+        codegen: false,
+        vue_version: self.vue_version,
+        custom_elements: self.custom_elements,
+        delimiters: self.delimiters,
+        custom_block_handlers: self.custom_block_handlers,
+        source_resolver: self.source_resolver,
+      },
+    )
+    .parse();
 
     if fatal {
       VueJsxParserReturn {
@@ -109,10 +307,185 @@ impl<'a> VueJsxParser<'a> {
         module_record, // Dummy one if fatal, can be directly passed there without recreate a new one
         errors,
         irregular_whitespaces: Box::new([]),
+        options_api: None,
+        external_sources,
+        external_errors,
+        virtual_documents,
         panicked: true,
       }
     } else {
-      VueJsxParserReturn { program, errors, panicked: false, irregular_whitespaces, module_record }
+      VueJsxParserReturn {
+        program,
+        errors,
+        panicked: false,
+        irregular_whitespaces,
+        module_record,
+        options_api,
+        external_sources,
+        external_errors,
+        virtual_documents,
+      }
     }
   }
 }
+
+impl<'a> VueJsxParser<'a> {
+  /// Parses only the `<script>` and `<script setup>` blocks, each into its own [`Program`], like
+  /// oxc's `PartialLoader`. The template is not transformed, so rules on the scripts alone run
+  /// with minimal overhead. Blocks with a `src` are skipped.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use oxc_allocator::Allocator;
+  /// use oxc_span::GetSpan;
+  /// use vue_oxlint_jsx::VueJsxParser;
+  ///
+  /// let allocator = Allocator::default();
+  /// let source = r#"<script setup lang="ts">const count: number = 1;</script>"#;
+  ///
+  /// let ret = VueJsxParser::new(&allocator, source).parse_scripts();
+  /// let script = &ret.scripts[0];
+  /// assert!(script.setup && script.source_type.is_typescript());
+  /// assert_eq!(&source[script.program.body[0].span()], "const count: number = 1;");
+  /// ```
+  #[must_use]
+  pub fn parse_scripts(self) -> VueScriptsReturn<'a> {
+    let ret = SfcDescriptor::parse(self.allocator, self.source_text);
+    let mut errors = ret.errors;
+    let mut blocks: Vec<_> =
+      [ret.descriptor.script, ret.descriptor.script_setup].into_iter().flatten().collect();
+    blocks.sort_unstable_by_key(|block| block.span.start);
+
+    let mut scripts = Vec::with_capacity(blocks.len());
+    for block in blocks {
+      if block.src().is_some() {
+        continue;
+      }
+      let lang = block.lang().unwrap_or("js");
+      let Ok(source_type) = SourceType::from_extension(lang) else {
+        error::unexpected_script_lang(&mut errors, lang);
+        continue;
+      };
+
+      let mut ret = oxc_parser::Parser::new(self.allocator, block.content, source_type)
+        .with_options(self.options)
+        .parse();
+
+      // The content is parsed on its own, then moved to its offset in the SFC
+      let start = block.content_span.start;
+      SpanShifter(start).visit_program(&mut ret.program);
+      ret.program.source_text = self.source_text;
+      for comment in &mut ret.program.comments {
+        comment.span = comment.span.move_right(start);
+        comment.attached_to += start;
+      }
+      ret.module_record.move_right(start);
+      for error in &mut ret.errors {
+        if let Some(labels) = &mut error.labels {
+          for label in labels.iter_mut() {
+            *label = LabeledSpan::new(
+              label.label().map(str::to_string),
+              label.offset() + start as usize,
+              label.len(),
+            );
+          }
+        }
+      }
+
+      scripts.push(VueScriptSection {
+        setup: block.attr("setup").is_some(),
+        start,
+        source_text: block.content,
+        source_type,
+        program: ret.program,
+        module_record: ret.module_record,
+        errors: ret.errors,
+        panicked: ret.panicked,
+      });
+    }
+    if scripts.windows(2).any(|pair| pair[0].source_type != pair[1].source_type) {
+      error::multiple_script_langs(&mut errors);
+    }
+
+    VueScriptsReturn { scripts, errors }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fmt::Write;
+
+  use oxc_allocator::Allocator;
+
+  use crate::{
+    VueJsxParser,
+    test::{format_node_locations, format_program_codegen, read_file, snapshot_name},
+  };
+
+  #[test]
+  fn parse_scripts() {
+    let file_path = "scripts/sections.vue";
+    let allocator = Allocator::default();
+    let source_text = read_file(file_path);
+    let ret = VueJsxParser::new(&allocator, &source_text).parse_scripts();
+    assert!(ret.errors.is_empty());
+
+    let mut result = String::new();
+    for script in &ret.scripts {
+      let modules: Vec<_> = script
+        .module_record
+        .requested_modules
+        .iter()
+        .flat_map(|(name, modules)| modules.iter().map(move |module| (name, module.span)))
+        .map(|(name, span)| (name, span.source_text(&source_text)))
+        .collect();
+      let _ = write!(
+        result,
+        "=============== Section ===============\n\nSetup: {}\nStart: {}\nSource Type: {:?}\nRequested Modules: {modules:?}\nErrors: {:?}\n\n{}\n{}",
+        script.setup,
+        script.start,
+        script.source_type,
+        script.errors,
+        format_program_codegen(&script.program),
+        format_node_locations(&script.program, &source_text, &[]),
+      );
+    }
+
+    let mut settings = insta::Settings::clone_current();
+    settings.set_snapshot_path("../test/snapshots/scripts");
+    settings.set_prepend_module_to_snapshot(false);
+    settings.bind(|| {
+      insta::assert_snapshot!(snapshot_name(file_path), result);
+    });
+  }
+
+  #[test]
+  fn parse_scripts_spans() {
+    let allocator = Allocator::default();
+    let source = "<template></template>\n<script>\n// hi\nlet a = ;\n</script>";
+    let ret = VueJsxParser::new(&allocator, source).parse_scripts();
+    let script = &ret.scripts[0];
+    assert_eq!(script.program.source_text, source);
+    assert_eq!(script.program.comments[0].span.source_text(source), "// hi");
+    let label = &script.errors[0].labels.as_ref().unwrap()[0];
+    assert_eq!(&source[label.offset()..], ";\n</script>");
+  }
+
+  #[test]
+  fn parse_scripts_reports_langs() {
+    let allocator = Allocator::default();
+    let source = "<script lang=\"ts\"></script><script setup lang=\"coffee\"></script>";
+    let ret = VueJsxParser::new(&allocator, source).parse_scripts();
+    assert_eq!(ret.scripts.len(), 1);
+    assert_eq!(ret.errors[0].message, "Unsupported lang coffee in <script> blocks.");
+
+    let source = "<script lang=\"ts\"></script><script setup></script>";
+    let ret = VueJsxParser::new(&allocator, source).parse_scripts();
+    assert_eq!(ret.scripts.len(), 2);
+    assert_eq!(
+      ret.errors[0].message,
+      "<script> and <script setup> must have the same language type."
+    );
+  }
+}
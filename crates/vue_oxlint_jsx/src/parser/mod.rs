@@ -1,30 +1,79 @@
-use std::ptr;
+use std::{ptr, sync::Arc};
 
-use oxc_allocator::{Allocator, CloneIn, Vec as ArenaVec};
+use oxc_allocator::{Allocator, Box as ArenaBox, CloneIn, Vec as ArenaVec};
 use oxc_ast::{
-  AstBuilder, Comment,
-  ast::{Directive, Program, Statement},
+  AstBuilder, Comment, NONE,
+  ast::{
+    Directive, Program, PropertyKey, PropertyKind, Statement, TSTypeParameterDeclaration,
+    VariableDeclarationKind,
+  },
 };
 use oxc_diagnostics::OxcDiagnostic;
 use oxc_parser::ParseOptions;
-use oxc_span::{SourceType, Span};
-use oxc_syntax::module_record::ModuleRecord;
-use rustc_hash::FxHashSet;
+use oxc_span::{SPAN, SourceType, Span};
+use oxc_syntax::{identifier::is_identifier_name, module_record::ModuleRecord};
+use rustc_hash::{FxHashMap, FxHashSet};
+pub use vue_oxlint_parser::Delimiters;
 
 mod codegen;
+mod css_modules;
+mod custom_blocks;
+mod custom_elements;
 mod elements;
 mod error;
+mod external;
+mod inline_template;
 mod interface;
 mod irregular_whitespaces;
 mod modules;
+pub mod options_api;
 mod parse;
+mod pug;
 mod script;
+mod style;
+mod virtual_documents;
 
-pub use interface::{VueJsxParser, VueJsxParserReturn};
+use css_modules::CssModule;
+pub use custom_blocks::{
+  CustomBlock, CustomBlockAttribute, CustomBlockHandler, CustomBlockHandlers, CustomBlockReturn,
+  I18nBlockHandler, SyntheticDeclaration, YamlBlockHandler,
+};
+pub use custom_elements::CustomElements;
+pub use external::{
+  ExternalSource, FileDiagnostic, FileId, FsSourceResolver, MemorySourceResolver, SourceResolver,
+};
+pub use inline_template::{InlineTemplate, InlineTemplates, InlineTemplatesReturn};
+pub use interface::{VueJsxParser, VueJsxParserReturn, VueScriptSection, VueScriptsReturn};
+pub use options_api::{
+  ComponentDefinition, ComponentMember, ComponentMemberKind, LocalRegistration,
+  OptionsApiComponent, ThisReference,
+};
+pub use virtual_documents::VirtualDocument;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ParseConfig {
   pub codegen: bool,
+  pub vue_version: VueVersion,
+  pub custom_elements: CustomElements,
+  /// A component can override them with the `delimiters` option, either in the Options API object
+  /// or in `defineOptions({ delimiters })`.
+  pub delimiters: Delimiters,
+  pub custom_block_handlers: CustomBlockHandlers,
+  /// Reads the `src` of `<script src>`, `<template src>` and `<style src>`. Without it, those
+  /// blocks are left empty.
+  pub source_resolver: Option<Arc<dyn SourceResolver>>,
+}
+
+/// The Vue major version whose template semantics the transform follows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VueVersion {
+  /// Vue 2 (including 2.7): `v-for` has a higher precedence than `v-if` on the same element, and
+  /// the Vue 2 only syntax (`slot-scope`, `.sync`, `<template functional>`) is supported, while
+  /// the Vue 3 only syntax (`v-model:arg`, `v-memo`, `defineModel()`) is reported.
+  V2,
+  /// Vue 3: `v-if` has a higher precedence than `v-for` on the same element.
+  #[default]
+  V3,
 }
 
 pub struct ScriptBlock<'a> {
@@ -51,7 +100,28 @@ pub struct ParserImpl<'a> {
 
   global: ScriptBlock<'a>,
   setup: ScriptBlock<'a>,
+  /// Type parameters from `<script setup generic="...">`, attached to the setup arrow function.
+  setup_type_parameters: Option<ArenaBox<'a, TSTypeParameterDeclaration<'a>>>,
   sfc_struct_jsx_statement: Option<Statement<'a>>,
+  /// Top-level binding names declared in `<script>` and `<script setup>`, used to resolve
+  /// template components and custom directives to their script-side declarations.
+  script_bindings: FxHashSet<&'a str>,
+  /// Options API component model of the plain `<script>` block.
+  options_api: Option<OptionsApiComponent<'a>>,
+  /// `<style module>` blocks, declared as objects of their classes.
+  css_modules: Vec<CssModule<'a>>,
+  /// Objects contributed by custom block handlers, merged by name.
+  custom_block_declarations: Vec<(&'a str, Vec<(&'a str, Span)>)>,
+  /// The resolved `src` of blocks, in the order they were read.
+  external_sources: Vec<ExternalSource<'a>>,
+  /// Diagnostics of the resolved files, in their own span spaces.
+  external_errors: Vec<FileDiagnostic>,
+  /// `<style>` and custom blocks, in source order.
+  virtual_documents: Vec<VirtualDocument>,
+  /// The content of `<template lang="pug">`.
+  pug_template: Option<Span>,
+  /// The heads of the elements of a Pug template, by element start.
+  pug_heads: FxHashMap<u32, Span>,
 
   clean_spans: FxHashSet<Span>,
 }
@@ -87,7 +157,17 @@ impl<'a> ParserImpl<'a> {
 
       global: ScriptBlock { directives: ast.vec(), statements: ast.vec() },
       setup: ScriptBlock { directives: ast.vec(), statements: ast.vec() },
+      setup_type_parameters: None,
       sfc_struct_jsx_statement: None,
+      script_bindings: FxHashSet::default(),
+      options_api: None,
+      css_modules: vec![],
+      custom_block_declarations: vec![],
+      external_sources: vec![],
+      external_errors: vec![],
+      virtual_documents: vec![],
+      pug_template: None,
+      pug_heads: FxHashMap::default(),
 
       clean_spans: FxHashSet::default(),
     }
@@ -99,6 +179,10 @@ pub struct ParserImplReturn<'a> {
   pub module_record: ModuleRecord<'a>,
   pub irregular_whitespaces: Box<[Span]>,
   pub clean_spans: FxHashSet<Span>,
+  pub options_api: Option<OptionsApiComponent<'a>>,
+  pub external_sources: Vec<ExternalSource<'a>>,
+  pub external_errors: Vec<FileDiagnostic>,
+  pub virtual_documents: Vec<VirtualDocument>,
 
   pub fatal: bool,
   pub errors: Vec<OxcDiagnostic>,
@@ -186,6 +270,49 @@ where
   }
 }
 
+impl<'a> ParserImpl<'a> {
+  /// Declare `const name = { key: "key", ... }` at the start of `<script setup>`, so it is defined
+  /// before any user code, with each key spanned at its definition (a CSS class, an i18n message,
+  /// ...).
+  pub fn declare_key_object(&mut self, name: &'a str, keys: &[(&'a str, Span)]) {
+    let ast = self.ast;
+    let properties = ast.vec_from_iter(keys.iter().map(|&(key, span)| {
+      let raw = ast.str(&format!("{key:?}"));
+      let property_key = if is_identifier_name(key) {
+        ast.property_key_static_identifier(span, key)
+      } else {
+        PropertyKey::from(ast.expression_string_literal(span, key, Some(raw)))
+      };
+      ast.object_property_kind_object_property(
+        SPAN,
+        PropertyKind::Init,
+        property_key,
+        ast.expression_string_literal(SPAN, key, Some(raw)),
+        false,
+        false,
+        false,
+      )
+    }));
+    let declarator = ast.variable_declarator(
+      SPAN,
+      VariableDeclarationKind::Const,
+      ast.binding_pattern_binding_identifier(SPAN, name),
+      NONE,
+      Some(ast.expression_object(SPAN, properties)),
+      false,
+    );
+    self.setup.statements.insert(
+      0,
+      Statement::from(ast.declaration_variable(
+        SPAN,
+        VariableDeclarationKind::Const,
+        ast.vec1(declarator),
+        false,
+      )),
+    );
+  }
+}
+
 #[macro_export]
 macro_rules! is_void_tag {
   ($name:ident) => {
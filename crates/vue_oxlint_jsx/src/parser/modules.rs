@@ -1,12 +1,15 @@
-use oxc_span::SPAN;
+use oxc_span::{SPAN, Span};
 use oxc_syntax::module_record::{
-  ExportEntry, ExportExportName, ExportImportName, ExportLocalName, ModuleRecord,
+  ExportEntry, ExportExportName, ExportImportName, ExportLocalName, ImportImportName, ModuleRecord,
+  NameSpan,
 };
+use rustc_hash::FxHashSet;
 
 use crate::parser::ParserImpl;
 
 pub trait Merge: Sized {
   fn merge_imports(&mut self, instance: Self);
+  fn merge_type_exports(&mut self, instance: &Self);
   fn merge(&mut self, instance: Self);
 }
 
@@ -30,13 +33,98 @@ impl Merge for ModuleRecord<'_> {
     self.dynamic_imports.append(&mut instance.dynamic_imports);
     self.import_metas.append(&mut instance.import_metas);
   }
+
+  // `export type` / `export interface` are the only exports allowed in <script setup>
+  fn merge_type_exports(&mut self, instance: &Self) {
+    let mut names = FxHashSet::default();
+    for entry in instance.local_export_entries.iter().filter(|entry| entry.is_type) {
+      if let ExportExportName::Name(name) = &entry.export_name {
+        names.insert(name.name);
+      }
+      self.local_export_entries.push(entry.clone());
+    }
+    self
+      .indirect_export_entries
+      .extend(instance.indirect_export_entries.iter().filter(|entry| entry.is_type).cloned());
+    self
+      .star_export_entries
+      .extend(instance.star_export_entries.iter().filter(|entry| entry.is_type).cloned());
+    self.exported_bindings.extend(
+      instance
+        .exported_bindings
+        .iter()
+        .filter(|(name, _)| names.contains(*name))
+        .map(|(k, v)| (*k, *v)),
+    );
+  }
+}
+
+/// Move all spans of a module record right by an offset, for a module parsed apart from the text
+/// before it.
+pub trait MoveRight {
+  fn move_right(&mut self, offset: u32);
+}
+
+impl MoveRight for ModuleRecord<'_> {
+  fn move_right(&mut self, offset: u32) {
+    let span = |span: &mut Span| *span = span.move_right(offset);
+    let name = |name: &mut NameSpan| span(&mut name.span);
+
+    for module in self.requested_modules.values_mut().flat_map(|modules| modules.iter_mut()) {
+      span(&mut module.statement_span);
+      span(&mut module.span);
+    }
+    for entry in &mut self.import_entries {
+      span(&mut entry.statement_span);
+      name(&mut entry.module_request);
+      name(&mut entry.local_name);
+      match &mut entry.import_name {
+        ImportImportName::Name(import_name) => name(import_name),
+        ImportImportName::Default(import_span) => span(import_span),
+        ImportImportName::NamespaceObject => {}
+      }
+    }
+    for entry in self
+      .local_export_entries
+      .iter_mut()
+      .chain(self.indirect_export_entries.iter_mut())
+      .chain(self.star_export_entries.iter_mut())
+    {
+      span(&mut entry.statement_span);
+      span(&mut entry.span);
+      entry.module_request.as_mut().map(name);
+      if let ExportImportName::Name(import_name) = &mut entry.import_name {
+        name(import_name);
+      }
+      match &mut entry.export_name {
+        ExportExportName::Name(export_name) => name(export_name),
+        ExportExportName::Default(export_span) => span(export_span),
+        ExportExportName::Null => {}
+      }
+      if let ExportLocalName::Name(local_name) | ExportLocalName::Default(local_name) =
+        &mut entry.local_name
+      {
+        name(local_name);
+      }
+    }
+    self.exported_bindings.values_mut().for_each(span);
+    for import in &mut self.dynamic_imports {
+      span(&mut import.span);
+      span(&mut import.module_request);
+    }
+    self.import_metas.iter_mut().for_each(span);
+  }
 }
 
 impl ParserImpl<'_> {
   pub fn fix_module_records(&mut self) {
     self.module_record.has_module_syntax = true;
 
-    if !self.module_record.local_export_entries.iter().any(|entry| entry.export_name.is_default()) {
+    let record = &self.module_record;
+    // `<script src>` re-exports the default export of its file
+    if !record.local_export_entries.iter().any(|entry| entry.export_name.is_default())
+      && !record.indirect_export_entries.iter().any(|entry| entry.export_name.is_default())
+    {
       // For no script or <script setup> only file
       self.module_record.local_export_entries.push(ExportEntry {
         span: SPAN,
@@ -65,5 +153,6 @@ mod tests {
   #[test]
   fn setup() {
     test_module_record!("modules/setup.vue");
+    test_module_record!("modules/setup-type-exports.vue");
   }
 }
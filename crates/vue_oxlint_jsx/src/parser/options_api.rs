@@ -0,0 +1,404 @@
+//! Options API component model, extracted from the plain `<script>` block.
+//!
+//! Recognises `export default { ... }`, `export default defineComponent({ ... })` and
+//! `export default Vue.extend({ ... })`, then collects every component member (props, emits,
+//! data, computed, methods, watchers, injections and setup bindings) together with the local
+//! `components` / `directives` registrations and all `this.x` usages inside the options.
+
+use oxc_allocator::{Allocator, Vec as ArenaVec};
+use oxc_ast::ast::{
+  Argument, ArrayExpressionElement, Class, Expression, Function, FunctionBody, MemberExpression,
+  ObjectExpression, ObjectPropertyKind, PropertyKey, Statement,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_span::{GetSpan, Span};
+use oxc_syntax::scope::ScopeFlags;
+
+/// How the component options object is declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentDefinition {
+  /// `export default { ... }`
+  ExportDefault,
+  /// `export default defineComponent({ ... })`
+  DefineComponent,
+  /// `export default Vue.extend({ ... })`
+  VueExtend,
+  /// `Vue.component('name', { ... })` or `app.component('name', { ... })`
+  GlobalComponent,
+  /// `new Vue({ ... })` or `createApp({ ... })`
+  RootInstance,
+  /// `components: { Foo: { ... } }` in the options of another component
+  LocalComponent,
+}
+
+/// The option a [`ComponentMember`] is declared in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentMemberKind {
+  Prop,
+  Emit,
+  Data,
+  Computed,
+  Method,
+  Watch,
+  Inject,
+  Setup,
+}
+
+/// A single member of the component instance, e.g. `count` in `data() { return { count: 0 } }`.
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentMember<'a> {
+  /// The name on the instance, camelized for props (`my-prop` is `myProp`).
+  pub name: &'a str,
+  pub kind: ComponentMemberKind,
+  /// Span of the member key (or string literal for array-style `props` / `emits` / `inject`).
+  pub span: Span,
+}
+
+/// A `components` / `directives` local registration, e.g. `MyButton: Button`.
+#[derive(Debug, Clone, Copy)]
+pub struct LocalRegistration<'a> {
+  /// The registered name, e.g. `MyButton`.
+  pub name: &'a str,
+  /// The identifier the registration points to, e.g. `Button`, if it is a plain identifier.
+  pub local: Option<&'a str>,
+  pub span: Span,
+}
+
+/// A `this.x` (or `this['x']`) access inside the component options.
+#[derive(Debug, Clone, Copy)]
+pub struct ThisReference<'a> {
+  pub name: &'a str,
+  /// Span of the accessed property.
+  pub span: Span,
+}
+
+/// The component model of an Options API component.
+#[derive(Debug)]
+pub struct OptionsApiComponent<'a> {
+  pub definition: ComponentDefinition,
+  /// Span of the options object expression.
+  pub span: Span,
+  /// The `name` option and the span of its string literal.
+  pub name: Option<(&'a str, Span)>,
+  pub members: Vec<ComponentMember<'a>>,
+  pub components: Vec<LocalRegistration<'a>>,
+  pub directives: Vec<LocalRegistration<'a>>,
+  /// The `delimiters: ['${', '}']` option, overriding the interpolation delimiters.
+  pub delimiters: Option<(&'a str, &'a str)>,
+  pub this_references: Vec<ThisReference<'a>>,
+}
+
+impl<'a> OptionsApiComponent<'a> {
+  /// Analyze the top-level statements of `<script>`, returning [`None`] if there is no component
+  /// options object.
+  #[must_use]
+  pub fn analyze(
+    allocator: &'a Allocator,
+    statements: &ArenaVec<'a, Statement<'a>>,
+  ) -> Option<Self> {
+    statements.iter().find_map(|statement| {
+      let Statement::ExportDefaultDeclaration(decl) = statement else { return None };
+      let (definition, object) = component_options(decl.declaration.as_expression()?)?;
+      Some(Self::from_object(allocator, definition, object))
+    })
+  }
+
+  /// Find a member by name.
+  #[must_use]
+  pub fn member(&self, name: &str) -> Option<&ComponentMember<'a>> {
+    self.members.iter().find(|member| member.name == name)
+  }
+
+  /// Find the local registration of a directive by its (kebab-case or camelCase) name.
+  #[must_use]
+  pub fn directive(&self, name: &str) -> Option<&LocalRegistration<'a>> {
+    self.directives.iter().find(|directive| registration_matches(directive.name, name))
+  }
+
+  /// Find the local registration of a component by its tag name.
+  #[must_use]
+  pub fn component(&self, name: &str) -> Option<&LocalRegistration<'a>> {
+    self.components.iter().find(|component| registration_matches(component.name, name))
+  }
+
+  pub(crate) fn from_object(
+    allocator: &'a Allocator,
+    definition: ComponentDefinition,
+    object: &ObjectExpression<'a>,
+  ) -> Self {
+    let mut component = Self {
+      definition,
+      span: object.span,
+      name: None,
+      members: vec![],
+      components: vec![],
+      directives: vec![],
+      delimiters: None,
+      this_references: vec![],
+    };
+
+    for property in &object.properties {
+      let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+      let Some(option) = key_name(&property.key) else { continue };
+      let value = property.value.without_parentheses();
+
+      match option {
+        "name" => {
+          if let Expression::StringLiteral(lit) = value {
+            component.name = Some((lit.value.as_str(), lit.span));
+          }
+        }
+        "props" => {
+          let start = component.members.len();
+          component.add_keys(value, ComponentMemberKind::Prop);
+          // Vue normalizes the names of props, so `my-prop` is `myProp` on the instance
+          for member in &mut component.members[start..] {
+            if member.name.contains('-') {
+              member.name = allocator.alloc_str(&camelize(member.name));
+            }
+          }
+        }
+        "emits" => component.add_keys(value, ComponentMemberKind::Emit),
+        "inject" => component.add_keys(value, ComponentMemberKind::Inject),
+        "computed" => component.add_keys(value, ComponentMemberKind::Computed),
+        "methods" => component.add_keys(value, ComponentMemberKind::Method),
+        "watch" => component.add_keys(value, ComponentMemberKind::Watch),
+        "data" => {
+          // A root instance (`new Vue({ data: {} })`) may use a plain object
+          if let Expression::ObjectExpression(object) = value
+            && definition == ComponentDefinition::RootInstance
+          {
+            component.add_object_keys(object, ComponentMemberKind::Data);
+          } else if let Some(object) = returned_object(value) {
+            component.add_object_keys(object, ComponentMemberKind::Data);
+          }
+        }
+        "setup" => {
+          if let Some(object) = returned_object(value) {
+            component.add_object_keys(object, ComponentMemberKind::Setup);
+          }
+        }
+        "components" => component.components = registrations(value),
+        "directives" => component.directives = registrations(value),
+        "delimiters" => component.delimiters = delimiters(value),
+        _ => {}
+      }
+
+      if matches!(option, "data" | "computed" | "methods" | "watch") {
+        let mut collector = ThisCollector { references: &mut component.this_references, depth: 0 };
+        collector.visit_expression(&property.value);
+      }
+    }
+
+    component
+  }
+
+  /// Add member names from either an array of strings or an object's keys.
+  fn add_keys(&mut self, value: &Expression<'a>, kind: ComponentMemberKind) {
+    match value {
+      Expression::ArrayExpression(array) => {
+        for element in &array.elements {
+          if let ArrayExpressionElement::StringLiteral(lit) = element {
+            self.members.push(ComponentMember { name: lit.value.as_str(), kind, span: lit.span });
+          }
+        }
+      }
+      Expression::ObjectExpression(object) => self.add_object_keys(object, kind),
+      _ => {}
+    }
+  }
+
+  fn add_object_keys(&mut self, object: &ObjectExpression<'a>, kind: ComponentMemberKind) {
+    for property in &object.properties {
+      let ObjectPropertyKind::ObjectProperty(property) = property else { continue };
+      if let Some(name) = key_name(&property.key) {
+        self.members.push(ComponentMember { name, kind, span: property.key.span() });
+      }
+    }
+  }
+}
+
+/// Unwrap `{}`, `defineComponent({})` and `Vue.extend({})` into the options object.
+pub fn component_options<'b, 'a>(
+  expression: &'b Expression<'a>,
+) -> Option<(ComponentDefinition, &'b ObjectExpression<'a>)> {
+  match expression.get_inner_expression() {
+    Expression::ObjectExpression(object) => Some((ComponentDefinition::ExportDefault, object)),
+    Expression::CallExpression(call) => {
+      let definition = if call.callee.is_specific_id("defineComponent") {
+        ComponentDefinition::DefineComponent
+      } else if call.callee.is_specific_member_access("Vue", "extend") {
+        ComponentDefinition::VueExtend
+      } else {
+        return None;
+      };
+      match call.arguments.first()? {
+        Argument::ObjectExpression(object) => Some((definition, object)),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+/// The object returned by `data() { return {} }`, `data: () => ({})` or `data: function () {}`.
+fn returned_object<'b, 'a>(value: &'b Expression<'a>) -> Option<&'b ObjectExpression<'a>> {
+  let body: &FunctionBody = match value {
+    Expression::FunctionExpression(function) => function.body.as_ref()?,
+    Expression::ArrowFunctionExpression(arrow) => {
+      if arrow.expression {
+        let Some(Statement::ExpressionStatement(stmt)) = arrow.body.statements.first() else {
+          return None;
+        };
+        let Expression::ObjectExpression(object) = stmt.expression.get_inner_expression() else {
+          return None;
+        };
+        return Some(object);
+      }
+      &arrow.body
+    }
+    _ => return None,
+  };
+
+  body.statements.iter().rev().find_map(|statement| {
+    let Statement::ReturnStatement(ret) = statement else { return None };
+    match ret.argument.as_ref()?.get_inner_expression() {
+      Expression::ObjectExpression(object) => Some(&**object),
+      _ => None,
+    }
+  })
+}
+
+/// The `delimiters` option of `defineOptions({ delimiters: ['${', '}'] })` in `<script setup>`.
+pub fn define_options_delimiters<'a>(
+  statements: &ArenaVec<'a, Statement<'a>>,
+) -> Option<(&'a str, &'a str)> {
+  statements.iter().find_map(|statement| {
+    let Statement::ExpressionStatement(stmt) = statement else { return None };
+    let Expression::CallExpression(call) = stmt.expression.get_inner_expression() else {
+      return None;
+    };
+    if !call.callee.is_specific_id("defineOptions") {
+      return None;
+    }
+    let Some(Argument::ObjectExpression(object)) = call.arguments.first() else { return None };
+    object.properties.iter().find_map(|property| {
+      let ObjectPropertyKind::ObjectProperty(property) = property else { return None };
+      (key_name(&property.key)? == "delimiters")
+        .then(|| delimiters(property.value.without_parentheses()))?
+    })
+  })
+}
+
+/// `['${', '}']`, ignoring empty delimiters which would never match.
+fn delimiters<'a>(value: &Expression<'a>) -> Option<(&'a str, &'a str)> {
+  let Expression::ArrayExpression(array) = value else { return None };
+  let [ArrayExpressionElement::StringLiteral(open), ArrayExpressionElement::StringLiteral(close)] =
+    array.elements.as_slice()
+  else {
+    return None;
+  };
+  (!open.value.is_empty() && !close.value.is_empty())
+    .then(|| (open.value.as_str(), close.value.as_str()))
+}
+
+fn registrations<'a>(value: &Expression<'a>) -> Vec<LocalRegistration<'a>> {
+  let Expression::ObjectExpression(object) = value else { return vec![] };
+  object
+    .properties
+    .iter()
+    .filter_map(|property| {
+      let ObjectPropertyKind::ObjectProperty(property) = property else { return None };
+      let name = key_name(&property.key)?;
+      let local = match property.value.get_inner_expression() {
+        Expression::Identifier(ident) => Some(ident.name.as_str()),
+        _ => None,
+      };
+      Some(LocalRegistration { name, local, span: property.key.span() })
+    })
+    .collect()
+}
+
+pub fn key_name<'a>(key: &PropertyKey<'a>) -> Option<&'a str> {
+  match key {
+    PropertyKey::StaticIdentifier(ident) => Some(ident.name.as_str()),
+    PropertyKey::StringLiteral(lit) => Some(lit.value.as_str()),
+    _ => None,
+  }
+}
+
+/// Vue resolves local registrations by their raw, camelized and capitalized names.
+fn registration_matches(registered: &str, name: &str) -> bool {
+  if registered == name {
+    return true;
+  }
+  let camelized = camelize(name);
+  if registered == camelized {
+    return true;
+  }
+  let mut chars = camelized.chars();
+  chars.next().is_some_and(|first| {
+    registered.strip_prefix(first.to_ascii_uppercase()) == Some(chars.as_str())
+  })
+}
+
+/// Vue's `camelize`, which upper-cases the word character following each dash.
+fn camelize(name: &str) -> String {
+  let mut result = String::with_capacity(name.len());
+  let mut chars = name.chars().peekable();
+  while let Some(ch) = chars.next() {
+    match chars.peek() {
+      Some(&next) if ch == '-' && (next.is_ascii_alphanumeric() || next == '_') => {
+        result.push(next.to_ascii_uppercase());
+        chars.next();
+      }
+      _ => result.push(ch),
+    }
+  }
+  result
+}
+
+/// Collect `this.x` accesses, skipping nested non-arrow functions which rebind `this`.
+struct ThisCollector<'r, 'a> {
+  references: &'r mut Vec<ThisReference<'a>>,
+  depth: u32,
+}
+
+impl<'a> Visit<'a> for ThisCollector<'_, 'a> {
+  fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+    // Only the option functions themselves (e.g. `methods: { foo() {} }`) are bound to the instance
+    if self.depth > 0 {
+      return;
+    }
+    self.depth += 1;
+    walk::walk_function(self, it, flags);
+    self.depth -= 1;
+  }
+
+  fn visit_class(&mut self, _it: &Class<'a>) {}
+
+  fn visit_member_expression(&mut self, it: &MemberExpression<'a>) {
+    if matches!(it.object(), Expression::ThisExpression(_))
+      && let Some((span, name)) = it.static_property_info()
+    {
+      self.references.push(ThisReference { name, span });
+    }
+    walk::walk_member_expression(self, it);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{test_ast, test_options_api};
+
+  #[test]
+  fn options_api() {
+    test_options_api!("options_api/basic.vue");
+    test_options_api!("options_api/define_component.vue");
+    test_options_api!("options_api/vue_extend.vue");
+    test_options_api!("options_api/components.vue");
+  }
+
+  test_ast!(options_api_basic_vue, "options_api/basic.vue");
+  test_ast!(options_api_components_vue, "options_api/components.vue");
+}
@@ -2,20 +2,30 @@ use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
-use oxc_allocator::{self, Dummy, Vec as ArenaVec};
-use oxc_ast::ast::{Directive, Expression, FormalParameterKind, JSXChild, Program, Statement};
+use oxc_allocator::{self, Box as ArenaBox, Dummy, TakeIn, Vec as ArenaVec};
+use oxc_ast::ast::{
+  Directive, Expression, FormalParameterKind, IdentifierReference, JSXChild, JSXExpression,
+  Program, Statement, TSTypeParameterDeclaration,
+};
 use oxc_ast::{AstBuilder, NONE};
+use oxc_ast_visit::{Visit, VisitMut};
 
+use oxc_diagnostics::OxcDiagnostic;
 use oxc_span::{SPAN, Span};
 use oxc_syntax::module_record::ModuleRecord;
+use rustc_hash::FxHashMap;
 use vue_compiler_core::SourceLocation;
-use vue_compiler_core::parser::{AstNode, Element, ParseOption, Parser, WhitespaceStrategy};
-use vue_compiler_core::scanner::{ScanOption, Scanner, TextMode};
+use vue_compiler_core::parser::{
+  AstNode, ElemProp, Element, ParseOption, Parser, WhitespaceStrategy,
+};
+use vue_compiler_core::scanner::{AttributeValue, ScanOption, Scanner, TextMode};
+use vue_compiler_core::util::prop_finder;
 
 use crate::is_void_tag;
-use crate::parser::error::OxcErrorHandler;
+use crate::parser::error::{self, OxcErrorHandler};
 use crate::parser::irregular_whitespaces::collect_irregular_whitespaces;
-use crate::parser::{ResParse, ResParseExt};
+use crate::parser::options_api::define_options_delimiters;
+use crate::parser::{Delimiters, OptionsApiComponent, ResParse, ResParseExt, VueVersion};
 
 use super::ParserImpl;
 use super::ParserImplReturn;
@@ -48,8 +58,13 @@ impl<'a> ParserImpl<'a> {
           errors,
           global,
           setup,
+          setup_type_parameters,
           sfc_struct_jsx_statement: sfc_return,
           clean_spans,
+          options_api,
+          external_sources,
+          external_errors,
+          virtual_documents,
           ..
         } = self;
 
@@ -65,12 +80,17 @@ impl<'a> ParserImpl<'a> {
               global.statements,
               setup.statements,
               setup.directives,
+              setup_type_parameters,
               sfc_return,
               ast,
             ),
           ),
           irregular_whitespaces: collect_irregular_whitespaces(source_text),
           clean_spans,
+          options_api,
+          external_sources,
+          external_errors,
+          virtual_documents,
           fatal: false,
           errors,
           module_record,
@@ -83,6 +103,10 @@ impl<'a> ParserImpl<'a> {
         module_record: ModuleRecord::new(self.allocator),
         irregular_whitespaces: Box::new([]),
         clean_spans: rustc_hash::FxHashSet::default(),
+        options_api: None,
+        external_sources: self.external_sources,
+        external_errors: self.external_errors,
+        virtual_documents: self.virtual_documents,
       },
     }
   }
@@ -91,6 +115,7 @@ impl<'a> ParserImpl<'a> {
     mut statements: ArenaVec<'a, Statement<'a>>,
     mut setup: ArenaVec<'a, Statement<'a>>,
     setup_directives: ArenaVec<'a, Directive<'a>>,
+    type_parameters: Option<ArenaBox<'a, TSTypeParameterDeclaration<'a>>>,
     sfc_return: Option<Statement<'a>>,
     ast: AstBuilder<'a>,
   ) -> ArenaVec<'a, Statement<'a>> {
@@ -109,60 +134,183 @@ impl<'a> ParserImpl<'a> {
 
     statements.push(ast.statement_expression(
       SPAN,
-      Expression::ArrowFunctionExpression(
-        ast.alloc_arrow_function_expression(SPAN, false, true, NONE, params, NONE, body),
-      ),
+      Expression::ArrowFunctionExpression(ast.alloc_arrow_function_expression(
+        SPAN,
+        false,
+        true,
+        type_parameters,
+        params,
+        NONE,
+        body,
+      )),
     ));
 
     statements
   }
 }
 
+/// Scan and parse the SFC with vue-compiler-core, returning [`None`] on unrecoverable errors.
+pub fn scan_sfc<'a>(
+  source_text: &'a str,
+  delimiters: &Delimiters,
+  errors: &mut Vec<OxcDiagnostic>,
+) -> Option<Vec<AstNode<'a>>> {
+  let parser = Parser::new(ParseOption {
+    whitespace: WhitespaceStrategy::Preserve,
+    is_void_tag: |name| is_void_tag!(name),
+    get_text_mode: |name| get_text_mode!(name),
+    ..Default::default()
+  });
+  let scanner = Scanner::new(ScanOption {
+    delimiters: (delimiters.open.clone(), delimiters.close.clone()),
+    get_text_mode: |name| get_text_mode!(name),
+  });
+
+  // error processing
+  let offsets = ByteOffsets::new(source_text);
+  let errors = RefCell::from(errors);
+  let panicked = RefCell::from(false);
+  let tokens =
+    scanner.scan(source_text, OxcErrorHandler::new(&errors, &panicked, offsets.as_ref()));
+  let mut result = parser.parse(tokens, OxcErrorHandler::new(&errors, &panicked, offsets.as_ref()));
+
+  if *panicked.borrow() {
+    return None;
+  }
+  if let Some(offsets) = &offsets {
+    result.children.iter_mut().for_each(|node| offsets.convert_node(node));
+  }
+  Some(result.children)
+}
+
+/// vue-compiler-core counts the offsets of its locations in chars rather than bytes, so they are
+/// converted for sources with non-ASCII text.
+pub struct ByteOffsets(Vec<usize>);
+
+impl ByteOffsets {
+  fn new(source_text: &str) -> Option<Self> {
+    (!source_text.is_ascii()).then(|| {
+      Self(
+        source_text
+          .char_indices()
+          .map(|(offset, _)| offset)
+          .chain(std::iter::once(source_text.len()))
+          .collect(),
+      )
+    })
+  }
+
+  pub fn convert(&self, location: &mut SourceLocation) {
+    location.start.offset = self.0[location.start.offset];
+    location.end.offset = self.0[location.end.offset];
+  }
+
+  fn convert_node(&self, node: &mut AstNode) {
+    match node {
+      AstNode::Element(element) => {
+        self.convert(&mut element.location);
+        for prop in &mut element.properties {
+          match prop {
+            ElemProp::Attr(attr) => {
+              self.convert(&mut attr.location);
+              self.convert(&mut attr.name_loc);
+              if let Some(value) = &mut attr.value {
+                self.convert(&mut value.location);
+              }
+            }
+            ElemProp::Dir(dir) => {
+              self.convert(&mut dir.location);
+              self.convert(&mut dir.head_loc);
+              if let Some(expression) = &mut dir.expression {
+                self.convert(&mut expression.location);
+              }
+            }
+          }
+        }
+        element.children.iter_mut().for_each(|child| self.convert_node(child));
+      }
+      AstNode::Text(text) => self.convert(&mut text.location),
+      AstNode::Interpolation(node) | AstNode::Comment(node) => self.convert(&mut node.location),
+    }
+  }
+}
+
 enum ParsingChild<'a> {
   Finish(JSXChild<'a>),
   Skip(Element<'a>),
 }
 
+/// The render context of a Vue 2 functional component.
+const FUNCTIONAL_CONTEXT: &[&str] =
+  &["props", "listeners", "data", "children", "parent", "slots", "scopedSlots", "injections"];
+
 impl<'a> ParserImpl<'a> {
   fn analyze(&mut self) -> ResParse<()> {
-    let parser = Parser::new(ParseOption {
-      whitespace: WhitespaceStrategy::Preserve,
-      is_void_tag: |name| is_void_tag!(name),
-      get_text_mode: |name| get_text_mode!(name),
-      ..Default::default()
-    });
-    let scanner =
-      Scanner::new(ScanOption { get_text_mode: |name| get_text_mode!(name), ..Default::default() });
-
-    // error processing
-    let errors = RefCell::from(&mut self.errors);
-    let panicked = RefCell::from(false);
     // get ast from vue-compiler-core
-    let tokens = scanner.scan(self.source_text, OxcErrorHandler::new(&errors, &panicked));
-    let result = parser.parse(tokens, OxcErrorHandler::new(&errors, &panicked));
-
-    if *panicked.borrow() {
+    self.find_pug_template();
+    let mut errors = vec![];
+    let nodes = self.scan_blocks(&mut errors);
+    self.errors.extend(errors);
+    let Some(nodes) = nodes else {
       return ResParse::panic();
-    }
+    };
+    let scan_errors = self.errors.len();
 
     let mut raw_children = vec![];
-    let mut text_start: u32 = 0;
     let mut source_types: HashSet<&str> = HashSet::new();
-    for child in result.children {
-      if let AstNode::Element(node) = child {
-        // Template text nodes are intentionally ignored.
-        text_start = node.location.end.offset as u32;
-
-        raw_children.push(if node.tag_name == "script" {
+    for child in nodes {
+      match child {
+        AstNode::Element(node) => raw_children.push(if node.tag_name == "script" {
           // Fill self.global, self.setup
           self.parse_script(&node, &mut source_types)?;
           ParsingChild::Finish(self.parse_element(node, Some(self.ast.vec())).0)
         } else {
           ParsingChild::Skip(node)
-        });
+        }),
+        // Text between blocks, kept so rules on literal text see it
+        AstNode::Text(text) => raw_children.push(ParsingChild::Finish(self.parse_text(&text))),
+        // Comments also keep two texts around them apart
+        AstNode::Comment(comment) => {
+          raw_children.push(ParsingChild::Finish(self.parse_comment(&comment)));
+        }
+        AstNode::Interpolation(_) => {}
+      }
+    }
+    self.options_api = OptionsApiComponent::analyze(self.allocator, &self.global.statements);
+
+    // The component's own `delimiters` option is only known once its script is parsed, so the
+    // other blocks are scanned again with it
+    if let Some((open, close)) = define_options_delimiters(&self.setup.statements)
+      .or_else(|| self.options_api.as_ref().and_then(|component| component.delimiters))
+      && (open, close)
+        != (self.config.delimiters.open.as_str(), self.config.delimiters.close.as_str())
+    {
+      self.config.delimiters = Delimiters::new(open, close);
+      self.errors.drain(..scan_errors);
+      let mut errors = vec![];
+      let Some(nodes) = self.scan_blocks(&mut errors) else {
+        return ResParse::panic();
+      };
+      self.errors.extend(errors);
+
+      // `<script>` and `<style>` are raw text, so the blocks start at the same offsets
+      let mut blocks: FxHashMap<usize, Element<'a>> = nodes
+        .into_iter()
+        .filter_map(|node| match node {
+          AstNode::Element(node) if node.tag_name != "script" => {
+            Some((node.location.start.offset, node))
+          }
+          _ => None,
+        })
+        .collect();
+      for child in &mut raw_children {
+        if let ParsingChild::Skip(node) = child
+          && let Some(block) = blocks.remove(&node.location.start.offset)
+        {
+          *node = block;
+        }
       }
     }
-    let _ = text_start;
 
     // Parse the skip ones
     let mut children: ArenaVec<'a, JSXChild<'a>> = self.ast.vec();
@@ -172,14 +320,42 @@ impl<'a> ParserImpl<'a> {
         ParsingChild::Finish(child) => child,
         ParsingChild::Skip(node) => {
           if node.tag_name == "template" {
-            self.parse_element(node, None).0
+            let functional = self.config.vue_version == VueVersion::V2
+              && prop_finder(&node, "functional").allow_empty().find().is_some();
+            // `<template src>` and `<template lang="pug">` are parsed on their own
+            let content =
+              self.parse_external_template(&node).or_else(|| self.parse_pug_template(&node));
+            let mut template = self.parse_element(node, None).0;
+            if let Some(children) = content
+              && let JSXChild::Element(element) = &mut template
+            {
+              element.children = children;
+            }
+            if functional && let JSXChild::Element(element) = &mut template {
+              element.children = self.wrap_functional_template(element.children.take_in(self.ast));
+            }
+            template
+          } else if node.tag_name == "style" {
+            self.collect_virtual_document(&node);
+            // Only recorded as a dependency, as the stylesheet is linted on its own
+            self.record_src(&node);
+            self.analyze_css_module(&node);
+            let bindings = self.parse_style_bindings(&node);
+            self.parse_element(node, Some(bindings)).0
           } else {
+            self.collect_virtual_document(&node);
+            self.handle_custom_block(&node);
             self.parse_element(node, Some(self.ast.vec())).0
           }
         }
       });
     }
 
+    self.finish_css_modules(&children);
+    if self.config.vue_version == VueVersion::V3 {
+      self.check_vue3_listeners(&children);
+    }
+    self.finish_custom_blocks();
     self.sort_errors_and_commends();
 
     self.sfc_struct_jsx_statement = Some(self.ast.statement_expression(
@@ -195,6 +371,81 @@ impl<'a> ParserImpl<'a> {
     ResParse::success(())
   }
 
+  /// Report `$listeners` in the template and `this.$listeners` in the Options API, which only
+  /// exist in Vue 2.
+  fn check_vue3_listeners(&mut self, children: &ArenaVec<'a, JSXChild<'a>>) {
+    let mut finder = ListenersFinder::default();
+    for child in children {
+      finder.visit_jsx_child(child);
+    }
+    if let Some(component) = &self.options_api {
+      finder.spans.extend(
+        component
+          .this_references
+          .iter()
+          .filter(|reference| reference.name == "$listeners")
+          .map(|reference| reference.span),
+      );
+    }
+    for span in finder.spans {
+      error::removed_listeners(&mut self.errors, span);
+    }
+  }
+
+  /// Vue 2 `<template functional>` renders with the functional context instead of a component
+  /// instance, so its children are wrapped in `({ props, listeners, ... }) => <>children</>`.
+  fn wrap_functional_template(
+    &self,
+    children: ArenaVec<'a, JSXChild<'a>>,
+  ) -> ArenaVec<'a, JSXChild<'a>> {
+    let ast = self.ast;
+    let properties = ast.vec_from_iter(FUNCTIONAL_CONTEXT.iter().map(|&name| {
+      ast.binding_property(
+        SPAN,
+        ast.property_key_static_identifier(SPAN, name),
+        ast.binding_pattern_binding_identifier(SPAN, name),
+        true,
+        false,
+      )
+    }));
+    let params = ast.formal_parameters(
+      SPAN,
+      FormalParameterKind::ArrowFormalParameters,
+      ast.vec1(ast.formal_parameter(
+        SPAN,
+        ast.vec(),
+        ast.binding_pattern_object_pattern(SPAN, properties, NONE),
+        NONE,
+        NONE,
+        false,
+        None,
+        false,
+        false,
+      )),
+      NONE,
+    );
+    let body = ast.function_body(
+      SPAN,
+      ast.vec(),
+      ast.vec1(ast.statement_expression(
+        SPAN,
+        ast.expression_jsx_fragment(
+          SPAN,
+          ast.jsx_opening_fragment(SPAN),
+          children,
+          ast.jsx_closing_fragment(SPAN),
+        ),
+      )),
+    );
+
+    ast.vec1(ast.jsx_child_expression_container(
+      SPAN,
+      JSXExpression::from(
+        ast.expression_arrow_function(SPAN, true, false, NONE, params, NONE, body),
+      ),
+    ))
+  }
+
   fn sort_errors_and_commends(&mut self) {
     self.comments.sort_by_key(|a| a.span.start);
     self.errors.sort_by(|a, b| {
@@ -209,6 +460,20 @@ impl<'a> ParserImpl<'a> {
   }
 }
 
+/// Collect the `$listeners` references.
+#[derive(Default)]
+struct ListenersFinder {
+  spans: Vec<Span>,
+}
+
+impl<'a> Visit<'a> for ListenersFinder {
+  fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+    if it.name == "$listeners" {
+      self.spans.push(it.span);
+    }
+  }
+}
+
 // Easy transform from vue_compiler_core::SourceLocation to oxc_span::Span
 pub trait SourceLocatonSpan {
   fn span(&self) -> Span;
@@ -220,6 +485,47 @@ impl SourceLocatonSpan for SourceLocation {
   }
 }
 
+/// The content of a block, from the end of its start tag to the start of its end tag.
+pub fn block_content_span(node: &Element, source_text: &str) -> Span {
+  let location = node.location.span();
+  if location.source_text(source_text).ends_with("/>") {
+    return Span::empty(location.end);
+  }
+  let tag_name_end = node.properties.last().map_or(
+    node.location.start.offset + 1 /* < */ + node.tag_name.len(),
+    |prop| match prop {
+      ElemProp::Attr(prop) => prop.location.end.offset,
+      ElemProp::Dir(prop) => prop.location.end.offset,
+    },
+  );
+  let bytes = source_text.as_bytes();
+  let start = memchr::memchr(b'>', &bytes[tag_name_end..location.end as usize])
+    .map_or(location.end, |i| (tag_name_end + i + 1) as u32);
+  let end = memchr::memrchr(b'<', &bytes[start as usize..location.end as usize])
+    .map_or(location.end, |i| start + i as u32);
+  Span::new(start, end)
+}
+
+/// Move the spans of nodes parsed apart from the text before them, like an external file or a
+/// `<script>` block, keeping the empty spans of generated nodes.
+pub struct SpanShifter(pub u32);
+
+impl VisitMut<'_> for SpanShifter {
+  fn visit_span(&mut self, it: &mut Span) {
+    if *it != SPAN {
+      *it = it.move_right(self.0);
+    }
+  }
+}
+
+/// The span of an attribute value without its quotes, as `<a b=c>` can leave them out.
+pub fn attr_value_span(value: &AttributeValue) -> Span {
+  let span = value.location.span();
+  let len = value.content.raw.len() as u32;
+  // +1 to skip the opening quote
+  Span::sized(if span.size() > len { span.start + 1 } else { span.start }, len)
+}
+
 #[cfg(test)]
 mod tests {
   use crate::test_ast;
@@ -238,9 +544,34 @@ mod tests {
   test_ast!(error_multiple_langs_vue, "error/multiple_langs.vue", true, true);
   test_ast!(error_multiple_scripts_vue, "error/multiple_scripts.vue", true, true);
   test_ast!(error_empty_multiple_scripts_vue, "error/empty_multiple_scripts.vue");
+  test_ast!(error_setup_exports_vue, "error/setup_exports.vue", true, false);
+  test_ast!(error_setup_generic_vue, "error/setup_generic.vue", true, false);
+  test_ast!(error_listeners_vue, "error/listeners.vue", true, false);
   test_ast!(scripts_basic_vue, "scripts/basic.vue");
   test_ast!(scripts_setup_vue, "scripts/setup.vue");
+  test_ast!(
+    delimiters_config_vue,
+    "delimiters/config.vue",
+    config: crate::parser::ParseConfig {
+      delimiters: crate::Delimiters::new("${", "}"),
+      ..Default::default()
+    }
+  );
+  test_ast!(delimiters_options_api_vue, "delimiters/options_api.vue");
+  test_ast!(delimiters_define_options_vue, "delimiters/define_options.vue");
+  test_ast!(delimiters_blocks_vue, "delimiters/blocks.vue");
   test_ast!(scripts_both_vue, "scripts/both.vue");
   test_ast!(scripts_empty_vue, "scripts/empty.vue");
   test_ast!(scripts_directives_vue, "scripts/directives.vue");
+  test_ast!(scripts_setup_type_exports_vue, "scripts/setup_type_exports.vue");
+  test_ast!(scripts_setup_generic_vue, "scripts/setup_generic.vue");
+  test_ast!(scripts_setup_generic_unquoted_vue, "scripts/setup_generic_unquoted.vue");
+  test_ast!(vue2_functional_vue, "vue2/functional.vue", vue_version: crate::VueVersion::V2);
+  test_ast!(
+    error_vue3_only_syntax_vue,
+    "error/vue3_only_syntax.vue",
+    true,
+    false,
+    config: crate::parser::ParseConfig { vue_version: crate::VueVersion::V2, ..Default::default() }
+  );
 }
@@ -0,0 +1,200 @@
+//! `<template lang="pug">`.
+//!
+//! The content of the template is hidden from vue-compiler-core, which would parse it as HTML,
+//! then parsed by [`vue_oxlint_parser::pug`] and turned into vue-compiler-core nodes. The rest of
+//! the transform handles those like the nodes of an HTML template, except for the spans of the
+//! tags, which are the heads of the Pug elements (see [`ParserImpl::parse_element`]).
+
+use oxc_allocator::Vec as ArenaVec;
+use oxc_ast::ast::JSXChild;
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+use vue_compiler_core::{
+  Namespace, Position, SourceLocation,
+  parser::{
+    AstNode, Directive, DirectiveArg, ElemProp, Element, ElementType, SourceNode, TextNode,
+  },
+  scanner::{Attribute, AttributeValue},
+  util::{VStr, is_core_component},
+};
+use vue_oxlint_parser::{
+  SfcDescriptor,
+  pug::{PugAttribute, PugElement, PugNode, parse_pug},
+};
+
+use crate::parser::{
+  ParserImpl,
+  parse::{SourceLocatonSpan, scan_sfc},
+};
+
+impl<'a> ParserImpl<'a> {
+  /// Find the content of `<template lang="pug">`, before the SFC is scanned.
+  pub fn find_pug_template(&mut self) {
+    // Skip the block scan for the SFCs which can't have one
+    if memchr::memmem::find(self.source_text.as_bytes(), b"pug").is_none() {
+      return;
+    }
+    let ret = SfcDescriptor::parse(self.allocator, self.origin_source_text);
+    self.pug_template = ret
+      .descriptor
+      .template
+      .filter(|template| template.lang() == Some("pug") && template.src().is_none())
+      .map(|template| template.content_span);
+  }
+
+  /// Scan the SFC with vue-compiler-core, with the content of `<template lang="pug">` blanked
+  /// out, keeping its line breaks.
+  pub fn scan_blocks(&mut self, errors: &mut Vec<OxcDiagnostic>) -> Option<Vec<AstNode<'a>>> {
+    let Some(span) = self.pug_template else {
+      return scan_sfc(self.source_text, &self.config.delimiters, errors);
+    };
+
+    // SAFETY: the bytes are replaced by ASCII spaces, which keeps the buffer valid UTF-8, and
+    // the buffer is restored right after the scan
+    unsafe {
+      let bytes = self.mut_ptr_source_text.cast::<u8>();
+      for i in span.start..span.end {
+        let byte = bytes.add(i as usize);
+        if !matches!(*byte, b'\n' | b'\r') {
+          byte.write(b' ');
+        }
+      }
+    }
+    let nodes = scan_sfc(self.source_text, &self.config.delimiters, errors);
+    self.sync_source_text();
+    nodes
+  }
+
+  /// Parse the content of `<template lang="pug">` into the children of the template.
+  pub fn parse_pug_template(&mut self, node: &Element<'a>) -> Option<ArenaVec<'a, JSXChild<'a>>> {
+    let span = self.pug_template.filter(|span| node.location.span().contains_inclusive(*span))?;
+    let delimiters = self.config.delimiters.clone();
+    let ret = parse_pug(
+      span.source_text(self.source_text),
+      span.start,
+      (&delimiters.open, &delimiters.close),
+    );
+    self.errors.extend(ret.errors);
+
+    let children = self.pug_nodes(ret.nodes);
+    Some(self.parse_children(span.start, span.end, children))
+  }
+
+  fn pug_nodes(&mut self, nodes: Vec<PugNode<'a>>) -> Vec<AstNode<'a>> {
+    let mut children: Vec<AstNode<'a>> = Vec::with_capacity(nodes.len());
+    for node in nodes {
+      // Adjacent texts, like the lines of a text block, are a single JSX text
+      if let PugNode::Text(text) = &node
+        && let Some(AstNode::Text(last)) = children.last_mut()
+      {
+        last.text.push(VStr::raw(text.text));
+        last.location.end.offset = text.span.end as usize;
+        continue;
+      }
+      children.push(self.pug_node(node));
+    }
+    children
+  }
+
+  fn pug_node(&mut self, node: PugNode<'a>) -> AstNode<'a> {
+    match node {
+      PugNode::Element(element) => AstNode::Element(self.pug_element(element)),
+      PugNode::Text(text) => AstNode::Text(TextNode {
+        text: std::iter::once(VStr::raw(text.text)).collect(),
+        location: location(text.span),
+      }),
+      PugNode::Interpolation(interpolation) => AstNode::Interpolation(SourceNode {
+        source: interpolation.expression,
+        location: location(interpolation.span),
+      }),
+      PugNode::Comment(comment) => {
+        AstNode::Comment(SourceNode { source: comment.value, location: location(comment.span) })
+      }
+    }
+  }
+
+  fn pug_element(&mut self, element: PugElement<'a>) -> Element<'a> {
+    self.pug_heads.insert(element.span.start, element.head_span);
+    let properties: Vec<_> = element.attributes.into_iter().map(pug_prop).collect();
+    let tag_type = element_type(element.name, &properties);
+    Element {
+      tag_name: element.name,
+      tag_type,
+      namespace: Namespace::Html,
+      properties,
+      children: self.pug_nodes(element.children),
+      location: location(element.span),
+    }
+  }
+}
+
+fn pug_prop(attribute: PugAttribute<'_>) -> ElemProp<'_> {
+  let directive = attribute.directive();
+  let value = attribute.value.map(|value| AttributeValue {
+    content: VStr::raw(value.value),
+    location: location(value.span),
+  });
+  let Some(directive) = directive else {
+    return ElemProp::Attr(Attribute {
+      name: attribute.name,
+      value,
+      name_loc: location(attribute.name_span),
+      location: location(attribute.span),
+    });
+  };
+
+  ElemProp::Dir(Directive {
+    name: directive.name,
+    argument: directive.argument.map(|argument| {
+      if argument.dynamic {
+        DirectiveArg::Dynamic(argument.name)
+      } else {
+        DirectiveArg::Static(argument.name)
+      }
+    }),
+    modifiers: directive.modifiers.iter().map(|&(modifier, _)| modifier).collect(),
+    expression: value,
+    head_loc: location(attribute.name_span),
+    // Starts at the name, after the quote of a quoted name
+    location: location(Span::new(attribute.name_span.start, attribute.span.end)),
+  })
+}
+
+/// The tag type vue-compiler-core gives an element, from its tag name and properties.
+fn element_type(name: &str, properties: &[ElemProp<'_>]) -> ElementType {
+  let has_directive = |names: &[&str]| {
+    properties.iter().any(|prop| matches!(prop, ElemProp::Dir(dir) if names.contains(&dir.name)))
+  };
+  if name == "slot" {
+    ElementType::SlotOutlet
+  } else if name == "template" && has_directive(&["if", "else", "else-if", "for", "slot"]) {
+    ElementType::Template
+  } else if name == "component"
+    || name.starts_with(|char: char| char.is_ascii_uppercase())
+    || is_core_component(name)
+    || has_directive(&["is"])
+    || properties.iter().any(|prop| {
+      matches!(prop, ElemProp::Attr(attr) if attr.name == "is"
+        && attr.value.as_ref().is_some_and(|value| value.content.raw.starts_with("vue:")))
+    })
+  {
+    ElementType::Component
+  } else {
+    ElementType::Plain
+  }
+}
+
+fn location(span: Span) -> SourceLocation {
+  SourceLocation {
+    start: Position { offset: span.start as usize, ..Position::default() },
+    end: Position { offset: span.end as usize, ..Position::default() },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(pug_vue, "pug/basic.vue");
+  test_ast!(pug_errors_vue, "pug/errors.vue", true, false);
+}
@@ -1,16 +1,20 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, path::Path};
 
-use oxc_allocator::Vec as ArenaVec;
-use oxc_ast::ast::Statement;
+use oxc_allocator::{Allocator, CloneIn, Vec as ArenaVec};
+use oxc_ast::ast::{CallExpression, Declaration, Expression, Statement};
+use oxc_ast_visit::{Visit, walk};
 
-use oxc_span::{GetSpan, SourceType};
+use oxc_span::{GetSpan, SourceType, Span};
+use rustc_hash::FxHashSet;
 use vue_compiler_core::{
   parser::{ElemProp, Element},
   util::{find_prop, prop_finder},
 };
 
 use crate::parser::{
-  ParserImpl, ResParse, ResParseExt, error, modules::Merge, parse::SourceLocatonSpan,
+  ParserImpl, ResParse, ResParseExt, VueVersion, error,
+  modules::Merge,
+  parse::{SourceLocatonSpan, attr_value_span},
 };
 
 impl<'a> ParserImpl<'a> {
@@ -19,11 +23,14 @@ impl<'a> ParserImpl<'a> {
     node: &Element<'a>,
     source_types: &mut HashSet<&'a str>,
   ) -> ResParse<()> {
+    let src = Self::block_src(node).map(|(src, _)| src);
     let lang = find_prop(node, "lang")
       .and_then(|p| match p.get_ref() {
         ElemProp::Attr(p) => p.value.as_ref().map(|value| value.content.raw),
         ElemProp::Dir(_) => None,
       })
+      // `<script src="./foo.ts">`
+      .or_else(|| src.and_then(|src| Path::new(src).extension()?.to_str()))
       .unwrap_or("js");
 
     source_types.insert(lang);
@@ -40,6 +47,18 @@ impl<'a> ParserImpl<'a> {
       return ResParse::panic();
     }
 
+    if src.is_some() {
+      if prop_finder(node, "setup").allow_empty().find().is_some() {
+        error::script_setup_src(&mut self.errors, node.location.span());
+      } else if self.script_set {
+        error::multiple_script_tags(&mut self.errors, node.location.span());
+        return ResParse::panic();
+      } else {
+        self.parse_external_script(node, self.source_type);
+      }
+      return ResParse::success(());
+    }
+
     // If there is at least one statements in the box
     if let Some(child) = node.children.first() {
       let span = child.get_location().span();
@@ -76,22 +95,39 @@ impl<'a> ParserImpl<'a> {
       for stmt in &body {
         self.clean_spans.insert(stmt.span());
       }
+      self.collect_script_bindings(&body);
 
       // Deal with modules record there
       if is_setup {
-        // Only merge imports, as exports are not allowed in <script setup>
+        self.parse_setup_generic(node);
+        if self.config.vue_version == VueVersion::V2 {
+          self.check_vue2_setup_macros(&body);
+        }
+        // Only merge imports and type exports, as other exports are not allowed in <script setup>
+        self.module_record.merge_type_exports(&module_record);
         self.module_record.merge_imports(module_record);
 
         // Append directives to setup block
         self.setup.directives.append(&mut directives);
 
-        // Split imports and other statements
+        // Split imports (and type-only exports) and other statements
         let mut imports: ArenaVec<Statement<'a>> = self.ast.vec();
         let mut statements: ArenaVec<Statement<'a>> = self.ast.vec();
 
         for statement in body {
           match statement {
             Statement::ImportDeclaration(_) => imports.push(statement),
+            _ if is_type_export(&statement) => imports.push(statement),
+            Statement::ExportNamedDeclaration(decl) => {
+              error::script_setup_exports(&mut self.errors, decl.span);
+              // Keep the local binding of `export const a = 1`
+              if let Some(declaration) = decl.unbox().declaration {
+                statements.push(Statement::from(declaration));
+              }
+            }
+            Statement::ExportDefaultDeclaration(_) | Statement::ExportAllDeclaration(_) => {
+              error::script_setup_exports(&mut self.errors, statement.span());
+            }
             _ => statements.push(statement),
           }
         }
@@ -111,4 +147,122 @@ impl<'a> ParserImpl<'a> {
 
     ResParse::success(())
   }
+
+  /// Parse `<script setup generic="T extends Item, K">` into the type parameters of the setup
+  /// arrow function, keeping the spans of the original attribute value.
+  fn parse_setup_generic(&mut self, node: &Element<'a>) {
+    let Some(span) = find_prop(node, "generic").and_then(|p| match p.get_ref() {
+      ElemProp::Attr(p) => p.value.as_ref().map(attr_value_span),
+      ElemProp::Dir(_) => None,
+    }) else {
+      return;
+    };
+
+    let allocator = Allocator::new();
+    // SAFETY: wrap with `(<` and `>()=>0)`
+    let type_parameters =
+      match unsafe { self.parse_expression(span, b"(<", b">()=>0)", &allocator) } {
+        Some(Expression::ArrowFunctionExpression(mut arrow)) => arrow.type_parameters.take(),
+        _ => None,
+      };
+
+    if let Some(type_parameters) = type_parameters {
+      self.setup_type_parameters = Some(type_parameters.clone_in(self.allocator));
+    } else {
+      error::invalid_script_setup_generic(&mut self.errors, span);
+    }
+  }
+
+  /// Report the `<script setup>` macros introduced by Vue 3, like `defineModel()`.
+  fn check_vue2_setup_macros(&mut self, body: &ArenaVec<'a, Statement<'a>>) {
+    let mut finder = MacroCallFinder { name: "defineModel", spans: vec![] };
+    finder.visit_statements(body);
+    for span in finder.spans {
+      error::vue3_only_syntax(&mut self.errors, "`defineModel()`", span);
+    }
+  }
+
+  /// Record the top-level bindings of a script block, see [`collect_bindings`].
+  pub fn collect_script_bindings(&mut self, body: &ArenaVec<'a, Statement<'a>>) {
+    collect_bindings(body, &mut self.script_bindings);
+  }
+}
+
+/// Record the names of all top-level bindings (imports and declarations, exported or not) in
+/// `body`.
+pub fn collect_bindings<'a>(body: &[Statement<'a>], bindings: &mut FxHashSet<&'a str>) {
+  for statement in body {
+    match statement {
+      Statement::ImportDeclaration(decl) => {
+        for specifier in decl.specifiers.iter().flatten() {
+          bindings.insert(specifier.local().name.as_str());
+        }
+      }
+      // `export const vFocus = {}` is a binding of the module too
+      Statement::ExportNamedDeclaration(decl) => {
+        if let Some(declaration) = &decl.declaration {
+          collect_declaration_bindings(declaration, bindings);
+        }
+      }
+      _ => {
+        if let Some(declaration) = statement.as_declaration() {
+          collect_declaration_bindings(declaration, bindings);
+        }
+      }
+    }
+  }
+}
+
+fn collect_declaration_bindings<'a>(
+  declaration: &Declaration<'a>,
+  bindings: &mut FxHashSet<&'a str>,
+) {
+  if let Declaration::VariableDeclaration(decl) = declaration {
+    for declarator in &decl.declarations {
+      for ident in declarator.id.get_binding_identifiers() {
+        bindings.insert(ident.name.as_str());
+      }
+    }
+  } else if let Some(ident) = declaration.id() {
+    bindings.insert(ident.name.as_str());
+  }
+}
+
+/// Collect the callee spans of the calls to a compiler macro.
+struct MacroCallFinder<'n> {
+  name: &'n str,
+  spans: Vec<Span>,
+}
+
+impl<'a> Visit<'a> for MacroCallFinder<'_> {
+  fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+    if it.callee.is_specific_id(self.name) {
+      self.spans.push(it.callee.span());
+    }
+    walk::walk_call_expression(self, it);
+  }
+}
+
+/// `export type X = ...`, `export interface X {}` and `export type { X }`, which can be hoisted out
+/// of `<script setup>` as they have no runtime value.
+fn is_type_export(statement: &Statement) -> bool {
+  match statement {
+    Statement::ExportNamedDeclaration(decl) => {
+      decl.export_kind.is_type()
+        || decl.declaration.as_ref().map_or_else(
+          || {
+            !decl.specifiers.is_empty()
+              && decl.specifiers.iter().all(|specifier| specifier.export_kind.is_type())
+          },
+          |declaration| {
+            matches!(
+              declaration,
+              Declaration::TSTypeAliasDeclaration(_) | Declaration::TSInterfaceDeclaration(_)
+            )
+          },
+        )
+    }
+    Statement::ExportAllDeclaration(decl) => decl.export_kind.is_type(),
+    _ => false,
+  }
 }
@@ -0,0 +1,129 @@
+use oxc_allocator::Vec as ArenaVec;
+use oxc_ast::ast::{JSXChild, JSXExpression};
+use oxc_span::Span;
+use vue_compiler_core::{
+  parser::{ElemProp, Element},
+  util::find_prop,
+};
+
+use crate::parser::{ParserImpl, parse::SourceLocatonSpan};
+
+impl<'a> ParserImpl<'a> {
+  /// Parse the CSS `v-bind()` expressions of a `<style>` block, like `v-bind(color)` or
+  /// `v-bind('theme.primary')`, into expression containers spanning each `v-bind()`, so the
+  /// script bindings they reference are used.
+  pub fn parse_style_bindings(&mut self, node: &Element<'a>) -> ArenaVec<'a, JSXChild<'a>> {
+    let mut children = self.ast.vec();
+    let (Some(first), Some(last)) = (node.children.first(), node.children.last()) else {
+      return children;
+    };
+    let content = Span::new(first.get_location().span().start, last.get_location().span().end);
+
+    let lang = find_prop(node, "lang").and_then(|p| match p.get_ref() {
+      ElemProp::Attr(p) => p.value.as_ref().map(|value| value.content.raw),
+      ElemProp::Dir(_) => None,
+    });
+    let line_comments = matches!(lang, Some("scss" | "sass" | "less" | "stylus" | "styl"));
+
+    for binding in css_v_binds(content.source_text(self.source_text), line_comments) {
+      let expression_span =
+        Span::new(content.start + binding.expression.0, content.start + binding.expression.1);
+      if let Some(expression) = self.parse_pure_expression(expression_span) {
+        children.push(self.ast.jsx_child_expression_container(
+          Span::new(content.start + binding.span.0, content.start + binding.span.1),
+          JSXExpression::from(expression),
+        ));
+      }
+    }
+
+    children
+  }
+}
+
+/// A `v-bind()` in CSS, with byte ranges relative to the style content.
+struct CssVBind {
+  /// `v-bind(...)`
+  span: (u32, u32),
+  /// The expression, without surrounding whitespace and quotes.
+  expression: (u32, u32),
+}
+
+/// Find the `v-bind()` calls in CSS, skipping comments like `@vue/compiler-sfc` does.
+///
+/// `line_comments` enables `//` comments, which are supported by Sass, Less and Stylus.
+fn css_v_binds(css: &str, line_comments: bool) -> Vec<CssVBind> {
+  let bytes = css.as_bytes();
+  let mut bindings = vec![];
+  let mut i = 0;
+
+  while i < bytes.len() {
+    let rest = &css[i..];
+    if rest.starts_with("/*") {
+      i = rest.find("*/").map_or(bytes.len(), |end| i + end + 2);
+    } else if line_comments && rest.starts_with("//") {
+      i = rest.find('\n').map_or(bytes.len(), |end| i + end);
+    } else if let Some(after_name) = rest.strip_prefix("v-bind") {
+      let name_start = i;
+      i += "v-bind".len();
+      let args = after_name.trim_start();
+      if !args.starts_with('(') {
+        continue;
+      }
+      let start = name_start + (rest.len() - args.len()) + 1;
+      let Some(close) = closing_paren(&css[start..]) else { continue };
+      let end = start + close;
+      if let Some(expression) = normalize_expression(css, start, end) {
+        bindings.push(CssVBind { span: (name_start as u32, end as u32 + 1), expression });
+      }
+      i = end + 1;
+    } else {
+      i += rest.chars().next().map_or(1, char::len_utf8);
+    }
+  }
+
+  bindings
+}
+
+/// The offset of the `)` closing a `v-bind(`, skipping quotes and nested parentheses.
+fn closing_paren(args: &str) -> Option<usize> {
+  let mut quote = None;
+  let mut depth = 0u32;
+  for (i, byte) in args.bytes().enumerate() {
+    match (quote, byte) {
+      (Some(q), _) if byte == q => quote = None,
+      (None, b'\'' | b'"') => quote = Some(byte),
+      (None, b'(') => depth += 1,
+      (None, b')') if depth == 0 => return Some(i),
+      (None, b')') => depth -= 1,
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Trim the argument of `v-bind()` and unwrap a quoted expression like `'theme.primary'`.
+fn normalize_expression(css: &str, start: usize, end: usize) -> Option<(u32, u32)> {
+  let raw = &css[start..end];
+  let trimmed = raw.trim();
+  if trimmed.is_empty() {
+    return None;
+  }
+  let mut start = start + (raw.len() - raw.trim_start().len());
+  let mut end = start + trimmed.len();
+  if trimmed.len() >= 2
+    && let Some(quote @ (b'\'' | b'"')) = trimmed.bytes().next()
+    && trimmed.ends_with(quote as char)
+  {
+    start += 1;
+    end -= 1;
+  }
+  Some((start as u32, end as u32))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::test_ast;
+
+  test_ast!(style_v_bind_vue, "style/v-bind.vue");
+  test_ast!(style_v_bind_non_ascii_vue, "style/v-bind-non-ascii.vue");
+}
@@ -0,0 +1,101 @@
+//! `<style>` and custom blocks as standalone documents.
+//!
+//! They are collected from the same scan as the rest of the SFC, so the tools linting them in
+//! their own languages (stylelint, a JSON schema, ...) get positions consistent with the JSX.
+
+use oxc_span::Span;
+use vue_compiler_core::{
+  parser::{ElemProp, Element},
+  util::prop_finder,
+};
+
+use crate::codegen::Mapping;
+use crate::parser::{ParserImpl, parse::block_content_span};
+
+/// A `<style>` or custom block as a standalone document.
+#[derive(Debug, Clone)]
+pub struct VirtualDocument {
+  /// The tag name of the block, e.g. `style` or `i18n`.
+  pub tag: String,
+  /// The `lang` attribute, defaulting to `css` for `<style>` and `txt` for custom blocks.
+  pub lang: String,
+  pub content: String,
+  /// The `src` attribute of an external block, whose content is empty.
+  pub src: Option<String>,
+  /// Whether a `<style>` block has the `scoped` attribute.
+  pub scoped: bool,
+  /// The injected name of a CSS Modules block, e.g. `$style`.
+  pub module: Option<String>,
+  /// Maps [`Self::content`] back to the SFC.
+  pub mappings: Vec<Mapping>,
+}
+
+impl<'a> ParserImpl<'a> {
+  /// Collect a `<style>` or custom block as a [`VirtualDocument`].
+  pub fn collect_virtual_document(&mut self, node: &Element<'a>) {
+    let attr = |name| {
+      prop_finder(node, name).allow_empty().find().map(|prop| match prop.get_ref() {
+        ElemProp::Attr(attr) => attr.value.as_ref().map_or("", |value| value.content.raw),
+        ElemProp::Dir(_) => "",
+      })
+    };
+    let default_lang = if node.tag_name == "style" { "css" } else { "txt" };
+    let lang = attr("lang").filter(|lang| !lang.is_empty()).unwrap_or(default_lang);
+
+    let content_span = block_content_span(node, self.source_text);
+    let mappings = if content_span.is_empty() {
+      vec![]
+    } else {
+      vec![Mapping::new(Span::sized(0, content_span.size()), content_span)]
+    };
+    self.virtual_documents.push(VirtualDocument {
+      tag: node.tag_name.to_string(),
+      lang: lang.to_string(),
+      content: content_span.source_text(self.source_text).to_string(),
+      src: Self::block_src(node).map(|(src, _)| src.to_string()),
+      scoped: attr("scoped").is_some(),
+      module: attr("module").map(|name| if name.is_empty() { "$style" } else { name }.to_string()),
+      mappings,
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use oxc_allocator::Allocator;
+
+  use crate::VueJsxParser;
+
+  #[test]
+  fn virtual_documents() {
+    let source = "<template><div /></template>\n\n<style scoped lang=\"scss\">\n.ä > .b { color: red; }\n</style>\n\n<style module=\"classes\" src=\"./b.css\"></style>\n\n<i18n lang=\"json\">{ \"a\": \"é\" }</i18n>\n";
+    let allocator = Allocator::default();
+    let ret = VueJsxParser::new(&allocator, source).parse();
+
+    let summary: Vec<_> = ret
+      .virtual_documents
+      .iter()
+      .map(|doc| {
+        (doc.tag.as_str(), doc.lang.as_str(), doc.src.as_deref(), doc.scoped, doc.module.as_deref())
+      })
+      .collect();
+    assert_eq!(
+      summary,
+      [
+        ("style", "scss", None, true, None),
+        ("style", "css", Some("./b.css"), false, Some("classes")),
+        ("i18n", "json", None, false, None),
+      ]
+    );
+
+    let [scoped, module, i18n] = ret.virtual_documents.as_slice() else { panic!() };
+    assert!(module.mappings.is_empty());
+    assert_eq!(scoped.content, "\n.ä > .b { color: red; }\n");
+    assert_eq!(i18n.content, "{ \"a\": \"é\" }");
+    for document in [scoped, i18n] {
+      let [mapping] = document.mappings.as_slice() else { panic!() };
+      assert_eq!(mapping.codegen_span.source_text(&document.content), document.content);
+      assert_eq!(mapping.original_span.source_text(source), document.content);
+    }
+  }
+}
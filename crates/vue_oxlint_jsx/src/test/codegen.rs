@@ -17,8 +17,20 @@ use oxc_ast_visit::Visit;
 use oxc_span::{ContentEq, GetSpan, SPAN, Span};
 
 pub fn run_codegen_test(file_path: &str) {
+  run_codegen_test_with_config(file_path, ParseConfig::default());
+}
+
+pub fn run_codegen_test_with_config(file_path: &str, config: ParseConfig) {
   let source_text = read_file(file_path);
-  let ret = VueJsxCodegen::new(&source_text).build();
+  let mut codegen = VueJsxCodegen::new(&source_text)
+    .with_vue_version(config.vue_version)
+    .with_custom_elements(config.custom_elements.clone())
+    .with_delimiters(config.delimiters.clone())
+    .with_custom_block_handlers(config.custom_block_handlers.clone());
+  if let Some(source_resolver) = &config.source_resolver {
+    codegen = codegen.with_source_resolver(source_resolver.clone());
+  }
+  let ret = codegen.build();
   assert!(!ret.panicked, "Codegen unexpectedly panicked for {file_path}");
 
   let snap_name = snapshot_name(file_path);
@@ -42,12 +54,13 @@ pub fn run_codegen_test(file_path: &str) {
     reparsed.errors,
   );
 
-  assert_reparsed_codegen_ast(file_path, &source_text, &reparsed.program, &ret.mappings);
+  assert_reparsed_codegen_ast(file_path, &source_text, config, &reparsed.program, &ret.mappings);
 }
 
 fn assert_reparsed_codegen_ast(
   file_path: &str,
   source_text: &str,
+  config: ParseConfig,
   reparsed_program: &oxc_ast::ast::Program<'_>,
   mappings: &[Mapping],
 ) {
@@ -56,7 +69,7 @@ fn assert_reparsed_codegen_ast(
     &allocator,
     source_text,
     ParseOptions::default(),
-    ParseConfig { codegen: true },
+    ParseConfig { codegen: true, ..config },
   )
   .parse();
 
@@ -1,5 +1,5 @@
 pub use crate::parser::ParserImplReturn;
-use crate::parser::{ParseConfig, ParserImpl};
+use crate::parser::{ExternalSource, ParseConfig, ParserImpl};
 use oxc_allocator::Allocator;
 use oxc_ast::ast::Program;
 use oxc_ast_visit::Visit;
@@ -12,6 +12,7 @@ mod codegen;
 
 pub use codegen::format_program_codegen;
 pub use codegen::run_codegen_test;
+pub use codegen::run_codegen_test_with_config;
 
 #[macro_export]
 macro_rules! test_ast {
@@ -28,6 +29,38 @@ macro_rules! test_ast {
       }
     }
   };
+  ($test_name:ident, $file_path:expr, vue_version: $vue_version:expr) => {
+    $crate::test_ast!(
+      $test_name,
+      $file_path,
+      config: $crate::parser::ParseConfig { vue_version: $vue_version, ..Default::default() }
+    );
+  };
+  ($test_name:ident, $file_path:expr, config: $config:expr) => {
+    mod $test_name {
+      fn config() -> $crate::parser::ParseConfig {
+        $config
+      }
+
+      #[test]
+      fn ast() {
+        $crate::test::run_ast_test_with_config($file_path, false, false, config());
+      }
+
+      #[test]
+      fn codegen() {
+        $crate::test::run_codegen_test_with_config($file_path, config());
+      }
+    }
+  };
+  ($test_name:ident, $file_path:expr, $should_errors:expr, $allow_panic:expr, config: $config:expr) => {
+    mod $test_name {
+      #[test]
+      fn ast() {
+        $crate::test::run_ast_test_with_config($file_path, $should_errors, $allow_panic, $config);
+      }
+    }
+  };
   ($test_name:ident, $file_path:expr, $should_errors:expr, $allow_panic:expr) => {
     mod $test_name {
       #[test]
@@ -47,6 +80,25 @@ macro_rules! test_module_record {
   }};
 }
 
+#[macro_export]
+macro_rules! test_options_api {
+  ($file_path:expr) => {{
+    $crate::test::run_test($file_path, "options_api", |ret| {
+      format!("Options API: {:#?}", ret.options_api)
+    });
+  }};
+}
+
+#[macro_export]
+macro_rules! test_manifest {
+  ($file_path:expr) => {{
+    $crate::test::run_test($file_path, "manifest", |ret| {
+      let manifest = $crate::ComponentManifest::from_program(&ret.program);
+      serde_json::to_string_pretty(&manifest).unwrap()
+    });
+  }};
+}
+
 pub struct TestResult<'a> {
   pub program: &'a Program<'a>,
   pub errors: &'a Vec<OxcDiagnostic>,
@@ -55,10 +107,19 @@ pub struct TestResult<'a> {
 }
 
 pub fn run_ast_test(file_path: &str, should_errors: bool, allow_panic: bool) {
-  run_test(file_path, "ast", |ret| {
+  run_ast_test_with_config(file_path, should_errors, allow_panic, ParseConfig::default());
+}
+
+pub fn run_ast_test_with_config(
+  file_path: &str,
+  should_errors: bool,
+  allow_panic: bool,
+  config: ParseConfig,
+) {
+  run_test_with_config(file_path, "ast", config, |ret| {
     let codegen = format_program_codegen(&ret.program);
     let source_text = read_file(file_path);
-    let node_locations = format_node_locations(&ret.program, &source_text);
+    let node_locations = format_node_locations(&ret.program, &source_text, &ret.external_sources);
     assert_eq!(
       !ret.errors.is_empty(),
       should_errors,
@@ -92,15 +153,20 @@ impl std::fmt::Debug for TestResult<'_> {
 }
 
 pub fn run_test<F>(file_path: &str, folder: &str, f: F)
+where
+  F: for<'a> FnOnce(&ParserImplReturn<'a>) -> String,
+{
+  run_test_with_config(file_path, folder, ParseConfig::default(), f);
+}
+
+pub fn run_test_with_config<F>(file_path: &str, folder: &str, config: ParseConfig, f: F)
 where
   F: for<'a> FnOnce(&ParserImplReturn<'a>) -> String,
 {
   let allocator = Allocator::default();
   let source_text = read_file(file_path);
 
-  let ret =
-    ParserImpl::new(&allocator, &source_text, ParseOptions::default(), ParseConfig::default())
-      .parse();
+  let ret = ParserImpl::new(&allocator, &source_text, ParseOptions::default(), config).parse();
 
   let result = f(&ret);
 
@@ -132,21 +198,27 @@ fn format_string_slice(s: &str) -> String {
   }
 }
 
-struct NodeLocationCollector<'a> {
+struct NodeLocationCollector<'a, 's> {
   source_text: &'a str,
+  external_sources: &'s [ExternalSource<'s>],
   locations: Vec<(Span, String, String)>,
 }
 
-impl<'a> NodeLocationCollector<'a> {
-  fn new(source_text: &'a str) -> Self {
-    Self { source_text, locations: Vec::new() }
+impl<'a, 's> NodeLocationCollector<'a, 's> {
+  fn new(source_text: &'a str, external_sources: &'s [ExternalSource<'s>]) -> Self {
+    Self { source_text, external_sources, locations: Vec::new() }
   }
 
   fn add_span(&mut self, span: Span, kind: String) {
-    let start = span.start as usize;
-    let end = span.end as usize;
     if !span.is_empty() {
-      let slice = &self.source_text[start..end];
+      // The nodes from external files are in their own span space
+      let slice = self
+        .external_sources
+        .iter()
+        .find_map(|source| {
+          source.local_span(span).map(|local| local.source_text(source.source_text))
+        })
+        .unwrap_or_else(|| span.source_text(self.source_text));
       let formatted_slice = format_string_slice(slice);
       let kind = match memchr::memchr(b'(', kind.as_bytes()) {
         Some(index) => kind[..index].to_owned(),
@@ -157,14 +229,18 @@ impl<'a> NodeLocationCollector<'a> {
   }
 }
 
-impl<'a> Visit<'a> for NodeLocationCollector<'a> {
+impl<'a> Visit<'a> for NodeLocationCollector<'a, '_> {
   fn enter_node(&mut self, kind: oxc_ast::AstKind<'a>) {
     self.add_span(kind.span(), format!("{kind:?}"));
   }
 }
 
-pub fn format_node_locations(program: &Program, source_text: &str) -> String {
-  let mut collector = NodeLocationCollector::new(source_text);
+pub fn format_node_locations(
+  program: &Program,
+  source_text: &str,
+  external_sources: &[ExternalSource],
+) -> String {
+  let mut collector = NodeLocationCollector::new(source_text, external_sources);
   collector.visit_program(program);
 
   let mut result = String::new();
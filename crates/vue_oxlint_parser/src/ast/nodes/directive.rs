@@ -14,39 +14,39 @@ use oxc_span::Span;
 #[derive(Debug)]
 pub struct VDirective<'a, 'b> {
   pub key: VDirectiveKey<'a, 'b>,
-  pub value: VDirectiveExpression<'a, 'b>,
-  pub modifiers: Vec<'a, VIdentifier<'a>>,
+  /// [`None`] for directives without a value, like `v-else`, or if the value fails to parse.
+  pub value: Option<VDirectiveExpression<'a, 'b>>,
   pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct VOnDirective<'a, 'b> {
   pub key: VDirectiveKey<'a, 'b>,
-  pub value: VOnExpression<'a, 'b>,
-  pub modifiers: Vec<'a, VIdentifier<'a>>,
+  pub value: Option<VOnExpression<'a, 'b>>,
   pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct VSlotDirective<'a, 'b> {
   pub key: VDirectiveKey<'a, 'b>,
-  pub value: VSlotExpression<'b>,
-  pub modifiers: Vec<'a, VIdentifier<'a>>,
+  pub value: Option<VSlotExpression<'b>>,
   pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct VForDirective<'a, 'b> {
   pub key: VDirectiveKey<'a, 'b>,
-  pub value: VForExpression<'a, 'b>,
-  pub modifiers: Vec<'a, VIdentifier<'a>>,
+  pub value: Option<VForExpression<'a, 'b>>,
   pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct VDirectiveKey<'a, 'b> {
   pub name: &'a VIdentifier<'a>,
-  pub argument: VDirectiveArgument<'a, 'b>,
+  /// `href` of `v-bind:href`, [`None`] without an argument, like `v-if`.
+  pub argument: Option<VDirectiveArgument<'a, 'b>>,
+  /// `.trim` / `.stop` modifiers, each spanning its name without the leading `.`
+  pub modifiers: Vec<'a, VIdentifier<'a>>,
   pub span: Span,
 }
 
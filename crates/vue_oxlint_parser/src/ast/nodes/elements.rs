@@ -5,7 +5,7 @@ use crate::ast::{
   bindings::Variable,
   nodes::{
     attribute::VAttribute,
-    javascript::{VInterpolation, VPureScript},
+    javascript::{VInterpolation, VPureScript, VStyleBinding},
   },
 };
 
@@ -16,6 +16,8 @@ pub enum VNode<'a, 'b> {
   Comment(Box<'a, VComment<'a>>),
   Interpolation(Box<'a, VInterpolation<'a, 'b>>),
   PureScript(Box<'a, VPureScript<'b>>),
+  /// CSS `v-bind()` in the raw text of a `<style>` element.
+  StyleBinding(Box<'a, VStyleBinding<'a, 'b>>),
 }
 
 #[derive(Debug)]
@@ -5,9 +5,11 @@
 //! We split `VExpressionContainer` into different kinds, but they are all serialized as `VExpressionContainer`
 //! - `VInterpolation`: `{{ }}`, can be treated as a `VNode`.
 //! - `VDirectiveExpression`: `v-bind`, `v-model`, `v-if` and most of the directives (including all custom directives).
+//! - `VFilterSequenceExpression`: Vue 2 filters (`{{ msg | capitalize }}`) in `VInterpolation` and `v-bind`.
 //! - `VOnExpression`: `v-on`, used in `v-on` directive, with body to storage statements inside.
 //! - `VForExpression`: `v-for`, used in `v-for` directive, with left and right to storage variable definitions and source.
 //! - `VSlotExpression`: `v-slot`, used in `v-slot` directive, with params to storage variable definitions.
+//! - `VStyleBinding`: CSS `v-bind()` in `<style>` blocks, can be treated as a `VNode` of the `<style>` element.
 //!
 //! We have to traverse nodes generated by `oxc_parser` as `VExpressionContainer` requires `references` field to trace variable bindings.
 //!
@@ -15,26 +17,55 @@
 //!
 //! It's only used in <script> and <script setup> blocks.
 
-use oxc_allocator::Vec;
-use oxc_ast::ast::{Directive, Expression, FormalParameters, Statement};
+use oxc_allocator::{Box, Vec};
+use oxc_ast::ast::{
+  Argument, Directive, Expression, FormalParameters, IdentifierReference, Statement,
+};
 use oxc_span::Span;
 
 use crate::ast::bindings::Reference;
 
 #[derive(Debug)]
 pub struct VInterpolation<'a, 'b> {
-  pub expression: Expression<'b>,
+  pub expression: VFilterableExpression<'a, 'b>,
   pub references: Vec<'a, Reference<'a>>,
   pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct VDirectiveExpression<'a, 'b> {
-  pub expression: Expression<'b>,
+  pub expression: VFilterableExpression<'a, 'b>,
   pub references: Vec<'a, Reference<'a>>,
   pub span: Span,
 }
 
+/// The expression of an interpolation or `v-bind`, which may use Vue 2 filters.
+#[derive(Debug)]
+pub enum VFilterableExpression<'a, 'b> {
+  Expression(Expression<'b>),
+  FilterSequence(Box<'a, VFilterSequenceExpression<'a, 'b>>),
+}
+
+/// Vue 2 filters, like `message | capitalize | truncate(10)`.
+///
+/// The source is split on the top-level `|` (skipping strings, template literals, regexes and
+/// `||`), then the expression and each filter are parsed by `oxc_parser`.
+#[derive(Debug)]
+pub struct VFilterSequenceExpression<'a, 'b> {
+  pub expression: Expression<'b>,
+  pub filters: Vec<'a, VFilter<'b>>,
+  pub span: Span,
+}
+
+/// A filter of a [`VFilterSequenceExpression`], like `truncate(10)`, which is called with the
+/// filtered value followed by `arguments`.
+#[derive(Debug)]
+pub struct VFilter<'b> {
+  pub callee: IdentifierReference<'b>,
+  pub arguments: Vec<'b, Argument<'b>>,
+  pub span: Span,
+}
+
 #[derive(Debug)]
 pub struct VDirectiveArgumentExpression<'a, 'b> {
   pub expression: Expression<'b>,
@@ -64,6 +95,17 @@ pub struct VSlotExpression<'b> {
   pub span: Span,
 }
 
+/// A CSS `v-bind()` in a `<style>` block, like `v-bind(color)` or `v-bind('theme.primary')`.
+///
+/// `span` covers the whole `v-bind(...)`, while `expression` is parsed from the argument without
+/// its quotes.
+#[derive(Debug)]
+pub struct VStyleBinding<'a, 'b> {
+  pub expression: Expression<'b>,
+  pub references: Vec<'a, Reference<'a>>,
+  pub span: Span,
+}
+
 #[derive(Debug)]
 pub struct VPureScript<'b> {
   pub statements: Vec<'b, Statement<'b>>,
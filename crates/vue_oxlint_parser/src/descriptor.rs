@@ -0,0 +1,439 @@
+//! Lightweight SFC descriptor, the equivalent of `@vue/compiler-sfc`'s
+//! `parse()`.
+//!
+//! Splits a `.vue` file into its top-level blocks — `<template>`,
+//! `<script>`, `<script setup>`, `<style>` and custom blocks — with their
+//! attributes and spans, without parsing any JavaScript or template syntax.
+//! Tooling such as block-order rules and file watchers can inspect the SFC
+//! structure through it cheaply.
+//!
+//! Like `@vue/compiler-sfc`, everything but an HTML `<template>` is read as
+//! raw text up to its close tag, so `<script>` bodies and custom blocks may
+//! contain anything.
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+
+use crate::lexer::{Lexer, VToken, VTokenKind};
+
+/// Result of [`SfcDescriptor::parse`].
+pub struct SfcDescriptorReturn<'a> {
+  pub descriptor: SfcDescriptor<'a>,
+  pub errors: Vec<OxcDiagnostic>,
+}
+
+/// The top-level blocks of a Vue SFC.
+#[derive(Debug, Default)]
+pub struct SfcDescriptor<'a> {
+  pub template: Option<SfcBlock<'a>>,
+  pub script: Option<SfcBlock<'a>>,
+  pub script_setup: Option<SfcBlock<'a>>,
+  pub styles: Vec<SfcBlock<'a>>,
+  /// Any other top-level element, like `<i18n>` or `<docs>`.
+  pub custom_blocks: Vec<SfcBlock<'a>>,
+}
+
+/// A top-level SFC block.
+#[derive(Debug)]
+pub struct SfcBlock<'a> {
+  /// The tag name as written, e.g. `script`.
+  pub tag: &'a str,
+  pub attrs: Vec<SfcAttribute<'a>>,
+  /// Raw text between the start and end tags.
+  pub content: &'a str,
+  /// Span of [`Self::content`], empty for self-closing blocks.
+  pub content_span: Span,
+  /// Span of the whole block, from `<` of the start tag to `>` of the end
+  /// tag.
+  pub span: Span,
+}
+
+/// An attribute of a block's start tag.
+#[derive(Debug)]
+pub struct SfcAttribute<'a> {
+  pub name: &'a str,
+  /// [`None`] for boolean attributes like `setup` or `scoped`.
+  pub value: Option<&'a str>,
+  pub span: Span,
+  /// Span of the value without its quotes.
+  pub value_span: Option<Span>,
+}
+
+impl<'a> SfcDescriptor<'a> {
+  /// Split `source_text` into its top-level blocks.
+  ///
+  /// Duplicated `<template>`, `<script>` or `<script setup>` blocks and
+  /// unclosed blocks are reported in [`SfcDescriptorReturn::errors`]; only
+  /// the first of each duplicate is kept.
+  #[must_use]
+  pub fn parse(allocator: &'a Allocator, source_text: &'a str) -> SfcDescriptorReturn<'a> {
+    let mut lexer = Lexer::new(allocator, source_text);
+    let mut descriptor = Self::default();
+    let mut errors = vec![];
+
+    while let Some(token) = lexer.next_token() {
+      let tokens = lexer.take_tokens();
+      match token.kind {
+        VTokenKind::HTMLTagOpen => {
+          let tag = Tag::read(&tokens, source_text);
+          let block = read_block(&mut lexer, source_text, tag, &mut errors);
+          descriptor.add_block(block, &mut errors);
+        }
+        VTokenKind::HTMLEndTagOpen => {
+          errors.push(OxcDiagnostic::error("Invalid end tag.").with_label(token.span));
+        }
+        // Text and comments between blocks
+        _ => {}
+      }
+    }
+    errors.extend(lexer.take_errors());
+
+    SfcDescriptorReturn { descriptor, errors }
+  }
+
+  /// All blocks in source order.
+  #[must_use]
+  pub fn blocks(&self) -> Vec<&SfcBlock<'a>> {
+    let mut blocks: Vec<_> = [&self.template, &self.script, &self.script_setup]
+      .into_iter()
+      .flatten()
+      .chain(&self.styles)
+      .chain(&self.custom_blocks)
+      .collect();
+    blocks.sort_unstable_by_key(|block| block.span.start);
+    blocks
+  }
+
+  fn add_block(&mut self, block: SfcBlock<'a>, errors: &mut Vec<OxcDiagnostic>) {
+    let (slot, name) = match block.tag {
+      "template" => (&mut self.template, "<template>"),
+      "script" if block.attr("setup").is_some() => (&mut self.script_setup, "<script setup>"),
+      "script" => (&mut self.script, "<script>"),
+      "style" => {
+        self.styles.push(block);
+        return;
+      }
+      _ => {
+        self.custom_blocks.push(block);
+        return;
+      }
+    };
+    if slot.is_some() {
+      errors.push(
+        OxcDiagnostic::error(format!("Single file component can contain only one {name} element"))
+          .with_label(block.span),
+      );
+    } else {
+      *slot = Some(block);
+    }
+  }
+}
+
+impl<'a> SfcBlock<'a> {
+  /// Find an attribute by name.
+  #[must_use]
+  pub fn attr(&self, name: &str) -> Option<&SfcAttribute<'a>> {
+    self.attrs.iter().find(|attr| attr.name == name)
+  }
+
+  /// The `lang` attribute, like `ts` or `scss`.
+  #[must_use]
+  pub fn lang(&self) -> Option<&'a str> {
+    self.attr("lang").and_then(|attr| attr.value)
+  }
+
+  /// The `src` attribute of an external block, like `./style.css`.
+  #[must_use]
+  pub fn src(&self) -> Option<&'a str> {
+    self.attr("src").and_then(|attr| attr.value)
+  }
+
+  /// Whether a `<style>` block has the `scoped` attribute.
+  #[must_use]
+  pub fn scoped(&self) -> bool {
+    self.attr("scoped").is_some()
+  }
+
+  /// The injected name of a CSS Modules `<style module>` block, `$style` if
+  /// the `module` attribute has no value.
+  #[must_use]
+  pub fn module(&self) -> Option<&'a str> {
+    self.attr("module").map(|attr| attr.value.filter(|value| !value.is_empty()).unwrap_or("$style"))
+  }
+}
+
+/// A start tag read from the tokens of one [`Lexer::next_token`] call.
+struct Tag<'a> {
+  name: &'a str,
+  attrs: Vec<SfcAttribute<'a>>,
+  self_closing: bool,
+  span: Span,
+}
+
+impl<'a> Tag<'a> {
+  /// `tokens` starts with `<` and ends with `>` or `/>`, unless the tag is
+  /// cut off by EOF.
+  fn read(tokens: &[VToken], source_text: &'a str) -> Self {
+    let name = tokens
+      .get(1)
+      .filter(|token| token.kind == VTokenKind::HTMLIdentifier)
+      .map_or("", |token| token.span.source_text(source_text));
+    let last = tokens.last().copied().unwrap_or(tokens[0]);
+
+    let mut attrs: Vec<SfcAttribute<'a>> = vec![];
+    // Whether the last attribute has seen `=` but not yet its value
+    let mut expects_value = false;
+    for token in tokens.iter().skip(2) {
+      match token.kind {
+        VTokenKind::HTMLIdentifier | VTokenKind::Punctuator | VTokenKind::HTMLLiteral => {
+          match attrs.last_mut() {
+            Some(attr) if expects_value => {
+              attr.value = Some(token.span.source_text(source_text));
+              attr.value_span = Some(token.span);
+              attr.span.end = close_quote_end(source_text, token.span.end);
+            }
+            // `:foo.bar` and unquoted `a.b` are split into several tokens
+            Some(attr) if attr.span.end == token.span.start => {
+              attr.span.end = token.span.end;
+              if let Some(value_span) = &mut attr.value_span {
+                value_span.end = token.span.end;
+                attr.value = Some(value_span.source_text(source_text));
+              } else {
+                attr.name = attr.span.source_text(source_text);
+              }
+            }
+            _ => attrs.push(SfcAttribute {
+              name: token.span.source_text(source_text),
+              value: None,
+              span: token.span,
+              value_span: None,
+            }),
+          }
+          expects_value = false;
+        }
+        VTokenKind::HTMLAssociation => {
+          if let Some(attr) = attrs.last_mut() {
+            // `=""` has no literal token
+            let start = token.span.end;
+            let empty = if matches!(source_text.as_bytes().get(start as usize), Some(b'"' | b'\''))
+            {
+              Span::new(start + 1, start + 1)
+            } else {
+              Span::new(start, start)
+            };
+            attr.value = Some("");
+            attr.value_span = Some(empty);
+            attr.span.end = close_quote_end(source_text, empty.end);
+            expects_value = true;
+          }
+        }
+        _ => {}
+      }
+    }
+
+    Self {
+      name,
+      attrs,
+      self_closing: last.kind == VTokenKind::HTMLSelfClosingTagClose,
+      span: Span::new(tokens[0].span.start, last.span.end),
+    }
+  }
+}
+
+/// Read the body and end tag of the block started by `tag`.
+fn read_block<'a>(
+  lexer: &mut Lexer<'a>,
+  source_text: &'a str,
+  tag: Tag<'a>,
+  errors: &mut Vec<OxcDiagnostic>,
+) -> SfcBlock<'a> {
+  let Tag { name, attrs, self_closing, span: start_tag } = tag;
+  let mut block = SfcBlock {
+    tag: name,
+    attrs,
+    content: "",
+    content_span: Span::empty(start_tag.end),
+    span: start_tag,
+  };
+  if self_closing {
+    return block;
+  }
+
+  let is_html_template = name == "template" && block.lang().is_none_or(|lang| lang == "html");
+  let (content_span, closed) = if is_html_template {
+    read_template_body(lexer, source_text, start_tag.end)
+  } else {
+    let content_span = lexer.lex_raw_text_until(&name.to_ascii_lowercase());
+    // The end tag, if it was found before EOF
+    let closed = lexer.next_token().is_some();
+    lexer.take_tokens();
+    (content_span, closed)
+  };
+
+  if !closed {
+    errors.push(OxcDiagnostic::error("Element is missing end tag.").with_label(start_tag));
+  }
+  block.content = content_span.source_text(source_text);
+  block.content_span = content_span;
+  block.span = Span::new(start_tag.start, lexer.position());
+  block
+}
+
+/// Lex an HTML `<template>` body up to its matching `</template>`, skipping
+/// nested `<template>` elements. Returns the content span and whether the
+/// end tag was found.
+fn read_template_body(lexer: &mut Lexer, source_text: &str, start: u32) -> (Span, bool) {
+  let mut depth = 0u32;
+  while let Some(token) = lexer.next_token() {
+    let tokens = lexer.take_tokens();
+    let is_template =
+      tokens.get(1).is_some_and(|name| name.span.source_text(source_text) == "template");
+    match token.kind {
+      VTokenKind::HTMLTagOpen
+        if is_template
+          && tokens.last().is_some_and(|last| last.kind == VTokenKind::HTMLTagClose) =>
+      {
+        depth += 1;
+      }
+      VTokenKind::HTMLEndTagOpen if is_template => {
+        if depth == 0 {
+          return (Span::new(start, token.span.start), true);
+        }
+        depth -= 1;
+      }
+      _ => {}
+    }
+  }
+  (Span::new(start, lexer.position()), false)
+}
+
+/// The end of an attribute whose value ends at `value_end`, including the
+/// closing quote.
+fn close_quote_end(source_text: &str, value_end: u32) -> u32 {
+  match source_text.as_bytes().get(value_end as usize) {
+    Some(b'"' | b'\'') => value_end + 1,
+    _ => value_end,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use oxc_allocator::Allocator;
+
+  use super::SfcDescriptor;
+
+  const SOURCE: &str = r#"<template>
+  <div><template v-if="ok">{{ msg }}</template></div>
+</template>
+
+<script lang="ts">
+export default { name: 'Foo' }
+</script>
+
+<script setup lang='ts' generic="T extends string">
+const msg = '</template>'
+</script>
+
+<style scoped>
+.a { color: red; }
+</style>
+<style module="classes" lang=scss src="./b.scss" />
+
+<i18n locale="en">{ "hello": "<b>Hello</b>" }</i18n>
+"#;
+
+  #[test]
+  fn blocks() {
+    let allocator = Allocator::default();
+    let ret = SfcDescriptor::parse(&allocator, SOURCE);
+    assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+    let descriptor = ret.descriptor;
+
+    let template = descriptor.template.as_ref().unwrap();
+    assert_eq!(template.content, "\n  <div><template v-if=\"ok\">{{ msg }}</template></div>\n");
+    assert!(template.span.source_text(SOURCE).ends_with("</div>\n</template>"));
+
+    let script = descriptor.script.as_ref().unwrap();
+    assert_eq!(script.lang(), Some("ts"));
+    assert_eq!(script.content, "\nexport default { name: 'Foo' }\n");
+
+    let setup = descriptor.script_setup.as_ref().unwrap();
+    assert_eq!(setup.lang(), Some("ts"));
+    assert_eq!(setup.attr("generic").and_then(|attr| attr.value), Some("T extends string"));
+    assert_eq!(setup.content, "\nconst msg = '</template>'\n");
+    assert!(setup.span.source_text(SOURCE).ends_with("</script>"));
+
+    let [scoped, module] = descriptor.styles.as_slice() else { panic!() };
+    assert!(scoped.scoped());
+    assert_eq!(scoped.module(), None);
+    assert_eq!(module.module(), Some("classes"));
+    assert_eq!(module.lang(), Some("scss"));
+    assert_eq!(module.src(), Some("./b.scss"));
+    assert_eq!(module.content, "");
+    assert_eq!(
+      module.span.source_text(SOURCE),
+      r#"<style module="classes" lang=scss src="./b.scss" />"#
+    );
+
+    let [i18n] = descriptor.custom_blocks.as_slice() else { panic!() };
+    assert_eq!(i18n.tag, "i18n");
+    assert_eq!(i18n.content, r#"{ "hello": "<b>Hello</b>" }"#);
+
+    let order: Vec<_> = descriptor.blocks().iter().map(|block| block.tag).collect();
+    assert_eq!(order, ["template", "script", "script", "style", "style", "i18n"]);
+  }
+
+  #[test]
+  fn attributes() {
+    let allocator = Allocator::default();
+    let source = r#"<style module lang = "scss" :foo.bar="x" data-empty=""></style>"#;
+    let ret = SfcDescriptor::parse(&allocator, source);
+    let style = &ret.descriptor.styles[0];
+
+    let attrs: Vec<_> = style
+      .attrs
+      .iter()
+      .map(|attr| (attr.name, attr.value, attr.span.source_text(source)))
+      .collect();
+    assert_eq!(
+      attrs,
+      [
+        ("module", None, "module"),
+        ("lang", Some("scss"), "lang = \"scss\""),
+        (":foo.bar", Some("x"), ":foo.bar=\"x\""),
+        ("data-empty", Some(""), "data-empty=\"\""),
+      ]
+    );
+    assert_eq!(style.module(), Some("$style"));
+  }
+
+  #[test]
+  fn raw_template() {
+    let allocator = Allocator::default();
+    let source = "<template lang=\"pug\">\ndiv(v-if=\"a < b\") </template>\n</template>";
+    let ret = SfcDescriptor::parse(&allocator, source);
+    let template = ret.descriptor.template.unwrap();
+    assert_eq!(template.content, "\ndiv(v-if=\"a < b\") ");
+    // The second `</template>` is a stray end tag
+    assert_eq!(ret.errors.len(), 1);
+  }
+
+  #[test]
+  fn errors() {
+    let allocator = Allocator::default();
+    let source = "<template></template><template></template><script>const a = 1";
+    let ret = SfcDescriptor::parse(&allocator, source);
+    let messages: Vec<_> = ret.errors.iter().map(|error| error.message.to_string()).collect();
+    assert_eq!(
+      messages,
+      [
+        "Single file component can contain only one <template> element",
+        "Element is missing end tag.",
+      ]
+    );
+    let descriptor = ret.descriptor;
+    assert_eq!(descriptor.template.unwrap().span, oxc_span::Span::new(0, 21));
+    assert_eq!(descriptor.script.unwrap().content, "const a = 1");
+  }
+}
@@ -11,6 +11,8 @@
 //!   recognised inside.
 //! - `v-pre` mode where `{{` / `}}` is treated as text rather than as
 //!   interpolation delimiters.
+//! - Custom interpolation delimiters (e.g. `${` / `}`) via
+//!   [`Lexer::with_delimiters`].
 //!
 //! The mode is set explicitly by the parser via [`Lexer::set_mode`] when it
 //! crosses element boundaries — the lexer does not infer it from the tag
@@ -62,6 +64,8 @@ pub struct Lexer<'a> {
   /// communicates which close tag terminates the body via this field.
   /// Stored lower-case ASCII.
   raw_close_tag: Option<&'a str>,
+  /// Interpolation open / close delimiters, `{{` / `}}` by default.
+  delimiters: (&'a [u8], &'a [u8]),
   tokens: ArenaVec<'a, VToken>,
   errors: Vec<OxcDiagnostic>,
 }
@@ -75,11 +79,23 @@ impl<'a> Lexer<'a> {
       pos: 0,
       mode: LexerMode::Data,
       raw_close_tag: None,
+      delimiters: (b"{{", b"}}"),
       tokens: ArenaVec::new_in(allocator),
       errors: Vec::new(),
     }
   }
 
+  /// Recognise `open` / `close` as the interpolation delimiters instead of
+  /// `{{` / `}}`, like Vue's `compilerOptions.delimiters`. The emitted
+  /// [`VTokenKind::VExpressionStart`] / [`VTokenKind::VExpressionEnd`] tokens
+  /// span the custom delimiters.
+  #[must_use]
+  pub const fn with_delimiters(mut self, open: &'a str, close: &'a str) -> Self {
+    debug_assert!(!open.is_empty() && !close.is_empty());
+    self.delimiters = (open.as_bytes(), close.as_bytes());
+    self
+  }
+
   #[must_use]
   pub const fn mode(&self) -> LexerMode {
     self.mode
@@ -135,15 +151,16 @@ impl<'a> Lexer<'a> {
     }
 
     // `{{` — interpolation start, only outside v-pre / rcdata / rawtext.
-    if self.mode.allow_interpolation() && b == b'{' && self.peek_byte_at(1) == Some(b'{') {
-      self.pos += 2;
+    let (open, close) = self.delimiters;
+    if self.mode.allow_interpolation() && self.starts_with(open) {
+      self.pos += open.len() as u32;
       return Some(self.emit(VTokenKind::VExpressionStart, start));
     }
 
     // `}}` — interpolation end. We emit it from the data state too; the
     // parser is responsible for pairing it with a preceding start.
-    if self.mode.allow_interpolation() && b == b'}' && self.peek_byte_at(1) == Some(b'}') {
-      self.pos += 2;
+    if self.mode.allow_interpolation() && self.starts_with(close) {
+      self.pos += close.len() as u32;
       return Some(self.emit(VTokenKind::VExpressionEnd, start));
     }
 
@@ -160,10 +177,8 @@ impl<'a> Lexer<'a> {
       if c == b'<' {
         break;
       }
-      if allow_interp && c == b'{' && self.source.get(p + 1) == Some(&b'{') {
-        break;
-      }
-      if allow_interp && c == b'}' && self.source.get(p + 1) == Some(&b'}') {
+      if allow_interp && (self.source[p..].starts_with(open) || self.source[p..].starts_with(close))
+      {
         break;
       }
       p += 1;
@@ -634,6 +649,28 @@ mod tests {
     );
   }
 
+  #[test]
+  fn custom_delimiters() {
+    let alloc = Allocator::default();
+    let src = "a ${ x } {{ y";
+    let mut lex = Lexer::new(&alloc, src).with_delimiters("${", "}");
+    lex.lex_all();
+    assert_eq!(
+      lex
+        .tokens
+        .iter()
+        .map(|t| (t.kind, &src[t.span.start as usize..t.span.end as usize]))
+        .collect::<Vec<_>>(),
+      vec![
+        (VTokenKind::HTMLText, "a "),
+        (VTokenKind::VExpressionStart, "${"),
+        (VTokenKind::HTMLText, " x "),
+        (VTokenKind::VExpressionEnd, "}"),
+        (VTokenKind::HTMLText, " {{ y"),
+      ],
+    );
+  }
+
   #[test]
   fn interpolation_off_in_v_pre() {
     let alloc = Allocator::default();
@@ -3,7 +3,10 @@
 //! See `rfcs/vue-oxlint-parser.md` for the design.
 
 pub mod ast;
+pub mod descriptor;
 pub mod lexer;
 pub mod parser;
+pub mod pug;
 
-pub use parser::{VueParseConfig, VueParser, VueParserReturn};
+pub use descriptor::{SfcAttribute, SfcBlock, SfcDescriptor, SfcDescriptorReturn};
+pub use parser::{Delimiters, VueParseConfig, VueParser, VueParserReturn};
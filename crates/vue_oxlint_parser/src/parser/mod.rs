@@ -3,6 +3,7 @@
 //! Phase 1 sets up the public surface and module structure; phases 3 and 4
 //! fill in the actual parsing logic.
 
+mod pug;
 mod script;
 mod template;
 
@@ -41,11 +42,37 @@ pub struct VueParserReturn<'a, 'b> {
   pub panicked: bool,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct VueParseConfig {
   /// Whether the consumer needs the parser to record `clean_spans`. The JSX
   /// crate sets this; the toolkit side doesn't need it.
   pub track_clean_spans: bool,
+  /// Interpolation delimiters, mirroring Vue's `compilerOptions.delimiters`.
+  pub delimiters: Delimiters,
+}
+
+/// The open and close delimiters of template interpolations, `{{` and `}}`
+/// by default.
+///
+/// Apps which render templates inside server-side templating languages
+/// usually switch to something like `${` / `}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delimiters {
+  pub open: String,
+  pub close: String,
+}
+
+impl Delimiters {
+  #[must_use]
+  pub fn new(open: impl Into<String>, close: impl Into<String>) -> Self {
+    Self { open: open.into(), close: close.into() }
+  }
+}
+
+impl Default for Delimiters {
+  fn default() -> Self {
+    Self::new("{{", "}}")
+  }
 }
 
 /// Vue SFC parser.
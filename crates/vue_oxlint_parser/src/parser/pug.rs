@@ -0,0 +1,335 @@
+//! `<template lang="pug">` handling.
+//!
+//! The content is parsed by [`crate::pug::parse_pug`], then each Pug node becomes the V-tree
+//! node of its HTML counterpart. Directive values and interpolations go through the same wrapped
+//! `oxc_parser` calls as HTML templates, so their spans point into the Pug source.
+
+use oxc_allocator::{Box as ArenaBox, TakeIn, Vec as ArenaVec};
+use oxc_ast::ast::{Expression, FormalParameters, Statement};
+use oxc_span::Span;
+
+use super::VueParser;
+use crate::{
+  ast::nodes::{
+    attribute::{VAttribute, VIdentifier, VLiteral, VPureAttribute},
+    directive::{
+      VDirective, VDirectiveArgument, VDirectiveKey, VForDirective, VOnDirective, VSlotDirective,
+    },
+    elements::{VComment, VElement, VNode, VStartTag, VText},
+    javascript::{
+      VDirectiveArgumentExpression, VDirectiveExpression, VFilterableExpression, VForExpression,
+      VInterpolation, VOnExpression, VSlotExpression,
+    },
+  },
+  pug::{PugAttribute, PugDirectiveArgument, PugElement, PugNode, parse_pug},
+};
+
+#[allow(dead_code, reason = "phase 4 will call this for `<template lang=\"pug\">`")]
+impl<'a, 'b> VueParser<'a, 'b>
+where
+  'b: 'a,
+{
+  /// Parse the content of `<template lang="pug">` at `span` into the children of the template.
+  pub(super) fn parse_pug_template(&mut self, span: Span) -> ArenaVec<'a, VNode<'a, 'b>> {
+    let delimiters = self.config.delimiters.clone();
+    let ret = parse_pug(
+      span.source_text(self.source_text),
+      span.start,
+      (&delimiters.open, &delimiters.close),
+    );
+    self.errors.extend(ret.errors);
+    self.pug_nodes(ret.nodes)
+  }
+
+  fn pug_nodes(&mut self, nodes: Vec<PugNode<'a>>) -> ArenaVec<'a, VNode<'a, 'b>> {
+    let allocator = self.allocator_a;
+    let mut children = ArenaVec::with_capacity_in(nodes.len(), allocator);
+    for node in nodes {
+      let node = match node {
+        PugNode::Element(element) => {
+          VNode::Element(ArenaBox::new_in(self.pug_element(element), allocator))
+        }
+        PugNode::Text(text) => {
+          VNode::Text(ArenaBox::new_in(VText { text: text.text, span: text.span }, allocator))
+        }
+        PugNode::Interpolation(interpolation) => {
+          let Some(expression) = self.parse_pug_expression(interpolation.expression_span) else {
+            continue;
+          };
+          VNode::Interpolation(ArenaBox::new_in(
+            VInterpolation {
+              expression: VFilterableExpression::Expression(expression),
+              references: ArenaVec::new_in(allocator),
+              span: interpolation.span,
+            },
+            allocator,
+          ))
+        }
+        PugNode::Comment(comment) => VNode::Comment(ArenaBox::new_in(
+          VComment { value: comment.value, span: comment.span },
+          allocator,
+        )),
+      };
+      children.push(node);
+    }
+    children
+  }
+
+  fn pug_element(&mut self, element: PugElement<'a>) -> VElement<'a, 'b> {
+    let allocator = self.allocator_a;
+    let mut attributes = ArenaVec::with_capacity_in(element.attributes.len(), allocator);
+    for attribute in element.attributes {
+      attributes.push(self.pug_attribute(attribute));
+    }
+
+    VElement {
+      name: element.name,
+      raw_name: element.name,
+      start_tag: VStartTag {
+        attributes,
+        self_closing: element.self_closing,
+        span: element.head_span,
+      },
+      children: self.pug_nodes(element.children),
+      // Pug elements are closed by indentation
+      end_tag: None,
+      variables: ArenaVec::new_in(allocator),
+      span: element.span,
+    }
+  }
+
+  fn pug_attribute(&mut self, attribute: PugAttribute<'a>) -> VAttribute<'a, 'b> {
+    let allocator = self.allocator_a;
+    let Some(directive) = attribute.directive() else {
+      return VAttribute::Normal(ArenaBox::new_in(
+        VPureAttribute {
+          key: VIdentifier {
+            name: attribute.name,
+            raw_name: attribute.name,
+            span: attribute.name_span,
+          },
+          value: attribute.value.map(|value| VLiteral { value: value.value, span: value.span }),
+          span: attribute.span,
+        },
+        allocator,
+      ));
+    };
+
+    let argument = directive.argument.and_then(|argument| self.pug_directive_argument(argument));
+    let mut modifiers = ArenaVec::with_capacity_in(directive.modifiers.len(), allocator);
+    for (name, span) in directive.modifiers {
+      modifiers.push(VIdentifier { name, raw_name: name, span });
+    }
+    let key = VDirectiveKey {
+      name: allocator.alloc(VIdentifier {
+        name: directive.name,
+        raw_name: directive.name_span.source_text(self.source_text),
+        span: directive.name_span,
+      }),
+      argument,
+      modifiers,
+      span: attribute.name_span,
+    };
+
+    let value = attribute
+      .value
+      .filter(|value| !value.value.trim().is_empty())
+      .map(|value| (value.value_span, value.span));
+    let span = attribute.span;
+    match directive.name {
+      "for" => {
+        let value = value.and_then(|(inner, outer)| self.parse_pug_v_for(inner, outer));
+        VAttribute::ForDirective(ArenaBox::new_in(VForDirective { key, value, span }, allocator))
+      }
+      "on" => {
+        let value = value.and_then(|(inner, outer)| {
+          let body = self.parse_pug_statements(inner)?;
+          Some(VOnExpression { body, references: ArenaVec::new_in(allocator), span: outer })
+        });
+        VAttribute::OnDirective(ArenaBox::new_in(VOnDirective { key, value, span }, allocator))
+      }
+      "slot" => {
+        let value = value.and_then(|(inner, outer)| {
+          let params = self.parse_pug_params(inner)?;
+          Some(VSlotExpression { params, span: outer })
+        });
+        VAttribute::SlotDirective(ArenaBox::new_in(VSlotDirective { key, value, span }, allocator))
+      }
+      _ => {
+        let value = value.and_then(|(inner, outer)| {
+          let expression = self.parse_pug_expression(inner)?;
+          Some(VDirectiveExpression {
+            expression: VFilterableExpression::Expression(expression),
+            references: ArenaVec::new_in(allocator),
+            span: outer,
+          })
+        });
+        VAttribute::Directive(ArenaBox::new_in(VDirective { key, value, span }, allocator))
+      }
+    }
+  }
+
+  fn pug_directive_argument(
+    &mut self,
+    argument: PugDirectiveArgument<'a>,
+  ) -> Option<VDirectiveArgument<'a, 'b>> {
+    let allocator = self.allocator_a;
+    if !argument.dynamic {
+      return Some(VDirectiveArgument::VIdentifier(ArenaBox::new_in(
+        VIdentifier { name: argument.name, raw_name: argument.name, span: argument.span },
+        allocator,
+      )));
+    }
+    let expression = self.parse_pug_expression(argument.span)?;
+    Some(VDirectiveArgument::VDirectiveArgument(ArenaBox::new_in(
+      VDirectiveArgumentExpression {
+        expression,
+        references: ArenaVec::new_in(allocator),
+        // With the brackets
+        span: argument.span.expand(1),
+      },
+      allocator,
+    )))
+  }
+
+  fn parse_pug_expression(&mut self, span: Span) -> Option<Expression<'b>> {
+    if span.source_text(self.source_text).trim().is_empty() {
+      return None;
+    }
+    self.parse_pure_expression(span, self.allocator_b)
+  }
+
+  fn parse_pug_statements(&mut self, span: Span) -> Option<ArenaVec<'b, Statement<'b>>> {
+    let mut ret = self.parse_program_region(span, b"", b"", self.allocator_b)?;
+    self.collect_script_comments(&ret.program.comments);
+    Some(ret.program.body.take_in(self.allocator_b))
+  }
+
+  /// The parameters of `(params) => 0`, for `v-slot` values and the left side of `v-for`.
+  fn parse_pug_params(&mut self, span: Span) -> Option<FormalParameters<'b>> {
+    let mut ret = self.parse_program_region(span, b"(", b")=>0", self.allocator_b)?;
+    self.collect_script_comments(&ret.program.comments);
+    let Some(Statement::ExpressionStatement(statement)) = ret.program.body.get_mut(0) else {
+      return None;
+    };
+    let Expression::ArrowFunctionExpression(arrow) = &mut statement.expression else {
+      return None;
+    };
+    Some(arrow.params.take_in(self.allocator_b))
+  }
+
+  /// `item in items`, `(item, index) of items`.
+  fn parse_pug_v_for(&mut self, inner: Span, outer: Span) -> Option<VForExpression<'a, 'b>> {
+    let source = inner.source_text(self.source_text);
+    let (alias_end, right_start) = split_v_for(source)?;
+    let left = source[..alias_end].trim();
+    let left = left.strip_prefix('(').and_then(|left| left.strip_suffix(')')).unwrap_or(left);
+    let left_start = left.as_ptr() as usize - source.as_ptr() as usize;
+    let left_span = Span::sized(inner.start + left_start as u32, left.len() as u32);
+    let right_span = Span::new(inner.start + right_start as u32, inner.end);
+
+    let left = self.parse_pug_params(left_span)?;
+    let right = self.parse_pug_expression(right_span)?;
+    Some(VForExpression {
+      left,
+      right,
+      references: ArenaVec::new_in(self.allocator_a),
+      span: outer,
+    })
+  }
+}
+
+/// Split a `v-for` value at its ` in ` / ` of `, like Vue's `forAliasRE`, returning the end of
+/// the alias and the start of the source.
+fn split_v_for(source: &str) -> Option<(usize, usize)> {
+  for (i, char) in source.char_indices() {
+    if !char.is_whitespace() {
+      continue;
+    }
+    let rest = source[i..].trim_start();
+    if (rest.starts_with("in") || rest.starts_with("of"))
+      && rest[2..].starts_with(char::is_whitespace)
+    {
+      return Some((i, source.len() - rest[2..].trim_start().len()));
+    }
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use oxc_allocator::Allocator;
+  use oxc_parser::ParseOptions;
+  use oxc_span::{GetSpan, Span};
+
+  use crate::{
+    ast::nodes::{
+      attribute::VAttribute, directive::VDirectiveArgument, elements::VNode,
+      javascript::VFilterableExpression,
+    },
+    parser::{VueParseConfig, VueParser},
+  };
+
+  const SOURCE: &str = "<template lang=\"pug\">\nul.list\n  li(v-for=\"(item, index) in items\" :key=\"item.id\" @click=\"select(item); log()\")\n    MyItem(#default=\"{ label }\" v-bind:[prop]=\"item\") {{ label }}!\n</template>\n";
+
+  fn content_span() -> Span {
+    let start = SOURCE.find('\n').unwrap() as u32 + 1;
+    Span::new(start, SOURCE.find("</template>").unwrap() as u32)
+  }
+
+  #[test]
+  fn builds_the_v_tree_of_pug_templates() {
+    let allocator_a = Allocator::new();
+    let allocator_b = Allocator::new();
+    let mut parser = VueParser::new(
+      &allocator_a,
+      &allocator_b,
+      SOURCE,
+      ParseOptions::default(),
+      VueParseConfig::default(),
+    );
+    let children = parser.parse_pug_template(content_span());
+    assert!(parser.errors.is_empty(), "{:?}", parser.errors);
+
+    let [VNode::Element(list)] = &children[..] else { panic!("expected one element") };
+    assert_eq!(list.name, "ul");
+    assert!(
+      matches!(&list.start_tag.attributes[..], [VAttribute::Normal(class)] if class.key.name == "class")
+    );
+
+    let [VNode::Element(item)] = &list.children[..] else { panic!("expected one element") };
+    let [VAttribute::ForDirective(v_for), VAttribute::Directive(key), VAttribute::OnDirective(on)] =
+      &item.start_tag.attributes[..]
+    else {
+      panic!("expected v-for, :key and @click");
+    };
+    let v_for = v_for.value.as_ref().unwrap();
+    assert_eq!(v_for.left.items.len(), 2);
+    assert_eq!(v_for.right.span().source_text(SOURCE), "items");
+    let Some(VFilterableExpression::Expression(key_expression)) =
+      key.value.as_ref().map(|value| &value.expression)
+    else {
+      panic!("expected a :key expression");
+    };
+    assert_eq!(key_expression.span().source_text(SOURCE), "item.id");
+    assert_eq!(on.value.as_ref().unwrap().body.len(), 2);
+
+    let [VNode::Element(component)] = &item.children[..] else { panic!("expected one element") };
+    assert_eq!(component.name, "MyItem");
+    let [VAttribute::SlotDirective(slot), VAttribute::Directive(bind)] =
+      &component.start_tag.attributes[..]
+    else {
+      panic!("expected #default and v-bind:[prop]");
+    };
+    assert_eq!(slot.value.as_ref().unwrap().params.items.len(), 1);
+    assert!(matches!(
+      &bind.key.argument,
+      Some(VDirectiveArgument::VDirectiveArgument(argument)) if argument.span.source_text(SOURCE) == "[prop]"
+    ));
+    assert!(matches!(
+      &component.children[..],
+      [VNode::Interpolation(interpolation), VNode::Text(text)]
+        if interpolation.span.source_text(SOURCE) == "{{ label }}" && text.text == "!"
+    ));
+  }
+}
@@ -255,7 +255,7 @@ where
     if ret.panicked { None } else { Some(ret) }
   }
 
-  fn collect_script_comments(&mut self, comments: &ArenaVec<'b, Comment>) {
+  pub(super) fn collect_script_comments(&mut self, comments: &ArenaVec<'b, Comment>) {
     let mut comments = comments.clone_in(self.allocator_a);
     self.script_comments.append(&mut comments);
   }
@@ -298,7 +298,7 @@ mod tests {
       allocator_b,
       source,
       ParseOptions::default(),
-      VueParseConfig { track_clean_spans: true },
+      VueParseConfig { track_clean_spans: true, ..Default::default() },
     )
   }
 
@@ -12,4 +12,7 @@
 //! - attribute / directive parsing, including dispatching embedded JS
 //!   regions (`v-bind`, `v-if`, `v-for`, `v-slot`, `v-on`, `{{ … }}`) to
 //!   `oxc_parser` via the wrap-and-reset trick on the `'b` arena
-//! - non-HTML preprocessor diagnostics for `<template lang="pug">` etc.
+//! - `<template lang="pug">`, parsed by [`crate::pug`] in `pug.rs`, and
+//!   diagnostics for the other non-HTML preprocessors
+//! - CSS `v-bind()` in `<style>` raw text, parsed at its original offset
+//!   into `VStyleBinding` children of the `<style>` element
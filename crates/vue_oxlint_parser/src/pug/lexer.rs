@@ -0,0 +1,702 @@
+//! Pug lexer.
+//!
+//! Works line by line like `pug-lexer`: the indentation of each line turns into
+//! [`PugTokenKind::Indent`], [`PugTokenKind::Newline`] or [`PugTokenKind::Outdent`] tokens, then
+//! the rest of the line into a tag head (`a.link#top(href="/")`), piped text, a comment or inline
+//! text. Text blocks (`p.`), comments and unsupported constructs consume their nested lines, so
+//! those never produce indentation tokens.
+
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+
+/// Pug keywords without a Vue template counterpart, skipped along with their nested lines.
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+  "append", "block", "case", "default", "doctype", "each", "else", "extends", "for", "if",
+  "include", "mixin", "prepend", "unless", "when", "while", "yield",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PugTokenKind {
+  /// A line more indented than the previous one.
+  Indent,
+  /// A line at the indentation of the previous one, which is an empty span at its start.
+  Newline,
+  /// The end of an indented block, which is an empty span.
+  Outdent,
+  /// `div`
+  Tag,
+  /// `#main`, whose value is `main`.
+  Id,
+  /// `.active`, whose value is `active`.
+  Class,
+  /// `(`
+  StartAttributes,
+  /// `href` or `:class`, whose value is unquoted for quoted names (`"@click"`).
+  AttributeName,
+  /// `"/home"` or `count + 1`, whose value is unquoted.
+  AttributeValue,
+  /// `)`
+  EndAttributes,
+  /// `/` of `img/`.
+  SlashClose,
+  /// `:` of a block expansion, like `li: a Home`.
+  Colon,
+  /// `.` starting a text block.
+  Dot,
+  /// `|` starting piped text.
+  Pipe,
+  Text,
+  /// `#{ expr }`, `!{ expr }`, `{{ expr }}` or `= expr`, whose value is the expression.
+  Interpolation,
+  /// `// comment` or `//- comment`, with its nested lines, whose value is after the slashes.
+  Comment,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PugToken<'s> {
+  pub kind: PugTokenKind,
+  pub span: Span,
+  /// The content of the token, without its punctuation.
+  pub value: &'s str,
+  pub value_span: Span,
+}
+
+pub struct PugLexer<'s, 'd> {
+  source: &'s str,
+  bytes: &'s [u8],
+  offset: u32,
+  delimiters: (&'d str, &'d str),
+  /// The widths of the open indentation levels, the innermost last.
+  indents: Vec<usize>,
+  /// `' '` or `'\t'`, after the first indented line.
+  indent_char: Option<u8>,
+  tokens: Vec<PugToken<'s>>,
+  errors: Vec<OxcDiagnostic>,
+}
+
+impl<'s, 'd> PugLexer<'s, 'd> {
+  /// Lex `source`, which starts at `offset` of the SFC.
+  #[must_use]
+  pub const fn new(source: &'s str, offset: u32) -> Self {
+    Self {
+      source,
+      bytes: source.as_bytes(),
+      offset,
+      delimiters: ("{{", "}}"),
+      indents: vec![],
+      indent_char: None,
+      tokens: vec![],
+      errors: vec![],
+    }
+  }
+
+  /// Use custom interpolation delimiters besides `#{` and `}`.
+  #[must_use]
+  pub const fn with_delimiters(mut self, open: &'d str, close: &'d str) -> Self {
+    self.delimiters = (open, close);
+    self
+  }
+
+  #[must_use]
+  pub fn lex(mut self) -> (Vec<PugToken<'s>>, Vec<OxcDiagnostic>) {
+    let mut pos = 0;
+    while pos < self.bytes.len() {
+      let end = self.line_end(pos);
+      let indent = self.indent(pos, end);
+      if pos + indent < end {
+        self.indentation(pos, indent);
+        let end = self.line(pos + indent, end, indent);
+        pos = self.next_line(end);
+      } else {
+        pos = self.next_line(end);
+      }
+    }
+
+    let end = self.bytes.len();
+    for _ in 1..self.indents.len() {
+      self.push(PugTokenKind::Outdent, end, end, end, end);
+    }
+    (self.tokens, self.errors)
+  }
+
+  const fn span(&self, start: usize, end: usize) -> Span {
+    Span::new(self.offset + start as u32, self.offset + end as u32)
+  }
+
+  fn error(&mut self, message: &str, start: usize, end: usize) {
+    self.errors.push(OxcDiagnostic::error(message.to_string()).with_label(self.span(start, end)));
+  }
+
+  fn push(
+    &mut self,
+    kind: PugTokenKind,
+    start: usize,
+    end: usize,
+    value_start: usize,
+    value_end: usize,
+  ) {
+    self.tokens.push(PugToken {
+      kind,
+      span: self.span(start, end),
+      value: &self.source[value_start..value_end],
+      value_span: self.span(value_start, value_end),
+    });
+  }
+
+  /// The end of the line containing `pos`, before its `\r\n` or `\n`.
+  fn line_end(&self, pos: usize) -> usize {
+    let end = memchr::memchr(b'\n', &self.bytes[pos..]).map_or(self.bytes.len(), |i| pos + i);
+    if end > pos && self.bytes[end - 1] == b'\r' { end - 1 } else { end }
+  }
+
+  /// The start of the line after the one containing `pos`.
+  fn next_line(&self, pos: usize) -> usize {
+    memchr::memchr(b'\n', &self.bytes[pos..]).map_or(self.bytes.len(), |i| pos + i + 1)
+  }
+
+  fn indent(&self, start: usize, end: usize) -> usize {
+    self.bytes[start..end].iter().take_while(|&&byte| matches!(byte, b' ' | b'\t')).count()
+  }
+
+  /// Emit the indentation tokens of a non-blank line.
+  fn indentation(&mut self, start: usize, indent: usize) {
+    if indent > 0 {
+      let chars = &self.bytes[start..start + indent];
+      let char = *self.indent_char.get_or_insert(chars[0]);
+      if chars.iter().any(|&byte| byte != char) {
+        self.error(
+          "Invalid indentation, you can use tabs or spaces but not both.",
+          start,
+          start + indent,
+        );
+        // Keep the line at the current level
+        self.push(PugTokenKind::Newline, start, start, start, start);
+        return;
+      }
+    }
+
+    let Some(&current) = self.indents.last() else {
+      // The first line sets the base indentation
+      self.indents.push(indent);
+      return;
+    };
+    if indent > current {
+      self.indents.push(indent);
+      self.push(PugTokenKind::Indent, start, start + indent, start, start + indent);
+      return;
+    }
+    if indent == current {
+      self.push(PugTokenKind::Newline, start, start, start, start);
+      return;
+    }
+    while self.indents.len() > 1 && self.indents.last().is_some_and(|&level| indent < level) {
+      self.indents.pop();
+      self.push(PugTokenKind::Outdent, start, start, start, start);
+    }
+    if self.indents.last() != Some(&indent) {
+      self.error("Inconsistent indentation.", start, start + indent);
+      if self.indents.len() == 1 {
+        self.push(PugTokenKind::Newline, start, start, start, start);
+      }
+    }
+  }
+
+  /// Lex the content of a line from `start` to `end`, returning where the lexed content ends,
+  /// which is after the nested lines of a block.
+  fn line(&mut self, start: usize, end: usize, indent: usize) -> usize {
+    let rest = &self.source[start..end];
+    if rest.starts_with("//") {
+      let value_start = start + if rest.starts_with("//-") { 3 } else { 2 };
+      let block_end = self.block_lines(end, indent).last().map_or(end, |line| line.end);
+      self.push(PugTokenKind::Comment, start, block_end, value_start, block_end);
+      return block_end;
+    }
+    if let Some(text) = rest.strip_prefix('|') {
+      self.push(PugTokenKind::Pipe, start, start + 1, start + 1, start + 1);
+      let text_start = if text.starts_with(' ') { start + 2 } else { start + 1 };
+      self.text(text_start, end);
+      return end;
+    }
+    if let Some(construct) = unsupported_construct(rest) {
+      self.error(&format!("`{construct}` is not supported in Vue templates."), start, end);
+      return self.block_lines(end, indent).last().map_or(end, |line| line.end);
+    }
+    self.tag_line(start, end, indent)
+  }
+
+  fn tag_line(&mut self, start: usize, mut end: usize, indent: usize) -> usize {
+    let mut pos = start;
+    loop {
+      let head_start = pos;
+      if let Some(len) = tag_name_len(&self.source[pos..end]) {
+        self.push(PugTokenKind::Tag, pos, pos + len, pos, pos + len);
+        pos += len;
+      }
+      loop {
+        let rest = &self.source[pos..end];
+        let marker = match rest.as_bytes().first() {
+          Some(b'#') => Some((PugTokenKind::Id, shorthand_len(&rest[1..], false))),
+          Some(b'.') => Some((PugTokenKind::Class, shorthand_len(&rest[1..], true))),
+          _ => None,
+        };
+        match marker {
+          Some((kind, len)) if len > 0 => {
+            self.push(kind, pos, pos + 1 + len, pos + 1, pos + 1 + len);
+            pos += 1 + len;
+          }
+          _ if rest.starts_with('(') => {
+            pos = self.attributes(pos);
+            end = self.line_end(pos);
+          }
+          _ if rest.starts_with("&attributes") => {
+            self.error("`&attributes` is not supported in Vue templates.", pos, end);
+            return end;
+          }
+          _ => break,
+        }
+      }
+
+      if pos == head_start {
+        self.error("Unexpected text, expected a tag.", pos, end);
+        return end;
+      }
+
+      if self.bytes.get(pos) == Some(&b'/') {
+        self.push(PugTokenKind::SlashClose, pos, pos + 1, pos, pos);
+        pos += 1;
+      }
+      let rest = &self.source[pos..end];
+      if rest.starts_with('.') && rest[1..].trim().is_empty() {
+        self.push(PugTokenKind::Dot, pos, pos + 1, pos + 1, pos + 1);
+        return self.text_block(end, indent);
+      }
+      if rest.starts_with(':') && rest[1..].starts_with([' ', '\t']) {
+        self.push(PugTokenKind::Colon, pos, pos + 1, pos + 1, pos + 1);
+        pos = end - rest[1..].trim_start().len();
+        continue;
+      }
+      if let Some(code) = rest.strip_prefix("!=").or_else(|| rest.strip_prefix('=')) {
+        let expression = code.trim();
+        let expression_start = end - code.trim_start().len();
+        let expression_end = expression_start + expression.len();
+        if expression.is_empty() {
+          self.error("Expected an expression after `=`.", pos, end);
+        } else {
+          self.push(PugTokenKind::Interpolation, pos, end, expression_start, expression_end);
+        }
+      } else if rest.starts_with(' ') {
+        self.text(pos + 1, end);
+      } else if let Some(char) = rest.chars().next() {
+        self.error(&format!("Unexpected character `{char}`."), pos, pos + char.len_utf8());
+      }
+      return end;
+    }
+  }
+
+  /// Lex an attribute list starting at the `(` at `open`, returning the position after its `)`.
+  fn attributes(&mut self, open: usize) -> usize {
+    self.push(PugTokenKind::StartAttributes, open, open + 1, open + 1, open + 1);
+    let len = self.bytes.len();
+    let mut pos = open + 1;
+    loop {
+      while pos < len && matches!(self.bytes[pos], b' ' | b'\t' | b'\n' | b'\r' | b',') {
+        pos += 1;
+      }
+      if pos >= len {
+        self.error("Unclosed attribute list.", open, open + 1);
+        return len;
+      }
+      if self.bytes[pos] == b')' {
+        self.push(PugTokenKind::EndAttributes, pos, pos + 1, pos, pos);
+        return pos + 1;
+      }
+
+      let name_start = pos;
+      let (value_start, value_end, name_end) = if matches!(self.bytes[pos], b'"' | b'\'') {
+        let Some(close) = self.string_end(pos) else {
+          self.error("Unterminated string.", pos, pos + 1);
+          return len;
+        };
+        (pos + 1, close, close + 1)
+      } else {
+        let end = self.attribute_name_end(pos);
+        (pos, end, end)
+      };
+      if name_end == name_start {
+        let char = self.source[pos..].chars().next().map_or(1, char::len_utf8);
+        self.error(
+          &format!("Unexpected character `{}`.", &self.source[pos..pos + char]),
+          pos,
+          pos + char,
+        );
+        pos += char;
+        continue;
+      }
+      self.push(PugTokenKind::AttributeName, name_start, name_end, value_start, value_end);
+      pos = name_end;
+
+      let mut equal = pos;
+      while equal < len && matches!(self.bytes[equal], b' ' | b'\t') {
+        equal += 1;
+      }
+      let equal_len = if self.bytes[equal..].starts_with(b"!=") {
+        2
+      } else if self.bytes.get(equal) == Some(&b'=') {
+        1
+      } else {
+        // A boolean attribute
+        continue;
+      };
+      pos = equal + equal_len;
+      while pos < len && matches!(self.bytes[pos], b' ' | b'\t' | b'\n' | b'\r') {
+        pos += 1;
+      }
+
+      if matches!(self.bytes.get(pos), Some(b'"' | b'\'' | b'`')) {
+        let Some(close) = self.string_end(pos) else {
+          self.error("Unterminated string.", pos, pos + 1);
+          return len;
+        };
+        self.push(PugTokenKind::AttributeValue, pos, close + 1, pos + 1, close);
+        pos = close + 1;
+      } else {
+        let end = self.expression_end(pos);
+        if end == pos {
+          self.error("Expected an attribute value.", equal, equal + equal_len);
+        } else {
+          self.push(PugTokenKind::AttributeValue, pos, end, pos, end);
+        }
+        pos = end;
+      }
+    }
+  }
+
+  /// The end of an unquoted attribute name, which may contain `[...]` dynamic arguments.
+  fn attribute_name_end(&self, start: usize) -> usize {
+    let mut bracket = false;
+    let mut pos = start;
+    while let Some(&byte) = self.bytes.get(pos) {
+      match byte {
+        b'[' => bracket = true,
+        b']' => bracket = false,
+        b' ' | b'\t' | b'\n' | b'\r' | b',' | b'=' | b')' if !bracket => break,
+        b'!' if !bracket && self.bytes.get(pos + 1) == Some(&b'=') => break,
+        _ => {}
+      }
+      pos += 1;
+    }
+    pos
+  }
+
+  /// The offset of the quote closing the string starting at `start`.
+  fn string_end(&self, start: usize) -> Option<usize> {
+    let quote = self.bytes[start];
+    let mut pos = start + 1;
+    while let Some(&byte) = self.bytes.get(pos) {
+      if byte == b'\\' {
+        pos += 1;
+      } else if byte == quote {
+        return Some(pos);
+      }
+      pos += 1;
+    }
+    None
+  }
+
+  /// The end of an unquoted JavaScript attribute value, at a top-level whitespace, `,` or `)`.
+  fn expression_end(&self, start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut pos = start;
+    while let Some(&byte) = self.bytes.get(pos) {
+      match byte {
+        b'"' | b'\'' | b'`' => match self.string_end(pos) {
+          Some(close) => pos = close,
+          None => return self.bytes.len(),
+        },
+        b'(' | b'[' | b'{' => depth += 1,
+        b')' | b']' | b'}' if depth > 0 => depth -= 1,
+        b' ' | b'\t' | b'\n' | b'\r' | b',' | b')' if depth == 0 => break,
+        _ => {}
+      }
+      pos += 1;
+    }
+    pos
+  }
+
+  /// The non-blank nested lines after the line ending at `end`, more indented than `indent`,
+  /// including the blank lines between them.
+  fn block_lines(&self, end: usize, indent: usize) -> Vec<BlockLine> {
+    let mut lines = vec![];
+    let mut pos = self.next_line(end);
+    while pos < self.bytes.len() {
+      let line_end = self.line_end(pos);
+      let line_indent = self.indent(pos, line_end);
+      if pos + line_indent < line_end && line_indent <= indent {
+        break;
+      }
+      lines.push(BlockLine { start: pos, indent: line_indent, end: line_end });
+      pos = self.next_line(line_end);
+    }
+    while lines.last().is_some_and(|line| line.start + line.indent == line.end) {
+      lines.pop();
+    }
+    lines
+  }
+
+  /// Lex the nested lines of a text block (`p.`) as text, keeping the indentation beyond the
+  /// one of the block.
+  fn text_block(&mut self, end: usize, indent: usize) -> usize {
+    let lines = self.block_lines(end, indent);
+    let base = lines
+      .iter()
+      .filter(|line| line.start + line.indent < line.end)
+      .map(|line| line.indent)
+      .min()
+      .unwrap_or_default();
+    for (i, line) in lines.iter().enumerate() {
+      let text_end = if i + 1 < lines.len() { self.next_line(line.end) } else { line.end };
+      self.text(line.start + line.indent.min(base), text_end);
+    }
+    lines.last().map_or(end, |line| line.end)
+  }
+
+  /// Lex text from `start` to `end`, splitting out its interpolations.
+  fn text(&mut self, start: usize, end: usize) {
+    let mut text_start = start;
+    let mut pos = start;
+    while pos < end {
+      let rest = &self.source[pos..end];
+      if rest.starts_with("\\#{") || rest.starts_with("\\!{") {
+        pos += 3;
+        continue;
+      }
+      let (open_len, close) = if rest.starts_with("#{") || rest.starts_with("!{") {
+        (2, None)
+      } else if !self.delimiters.0.is_empty() && rest.starts_with(self.delimiters.0) {
+        (self.delimiters.0.len(), Some(self.delimiters.1))
+      } else {
+        pos += rest.chars().next().map_or(1, char::len_utf8);
+        continue;
+      };
+
+      let expression_start = pos + open_len;
+      let expression = &self.source[expression_start..end];
+      let expression_end =
+        close.map_or_else(|| closing_brace(expression), |close| expression.find(close));
+      let Some(expression_end) = expression_end.map(|i| expression_start + i) else {
+        self.error("Unclosed interpolation.", pos, expression_start);
+        break;
+      };
+      let token_end = expression_end + close.map_or(1, str::len);
+      if text_start < pos {
+        self.push(PugTokenKind::Text, text_start, pos, text_start, pos);
+      }
+      self.push(PugTokenKind::Interpolation, pos, token_end, expression_start, expression_end);
+      pos = token_end;
+      text_start = pos;
+    }
+    if text_start < end {
+      self.push(PugTokenKind::Text, text_start, end, text_start, end);
+    }
+  }
+}
+
+struct BlockLine {
+  start: usize,
+  indent: usize,
+  end: usize,
+}
+
+/// The keyword or the leading character of a construct without a Vue template counterpart.
+fn unsupported_construct(line: &str) -> Option<&str> {
+  if line.starts_with(['-', '+', '<', ':']) {
+    return Some(&line[..1]);
+  }
+  let word_len = tag_name_len(line)?;
+  let word = &line[..word_len];
+  (UNSUPPORTED_KEYWORDS.contains(&word)
+    && matches!(line.as_bytes().get(word_len), None | Some(b' ' | b'\t')))
+  .then_some(word)
+}
+
+/// The length of the tag name at the start of `source`, like `div`, `my-button` or `svg:path`.
+fn tag_name_len(source: &str) -> Option<usize> {
+  if !source.starts_with(|char: char| char.is_ascii_alphanumeric() || char == '_') {
+    return None;
+  }
+  let name = source
+    .find(|char: char| !(char.is_ascii_alphanumeric() || matches!(char, '_' | '-' | ':')))
+    .map_or(source, |end| &source[..end]);
+  Some(name.trim_end_matches(['-', ':']).len())
+}
+
+/// The length of the class or id name at the start of `source`. Class names need a letter or
+/// `_`, so `.5` isn't one.
+fn shorthand_len(source: &str, class: bool) -> usize {
+  let len = source
+    .find(|char: char| !(char.is_ascii_alphanumeric() || matches!(char, '_' | '-')))
+    .unwrap_or(source.len());
+  if class && !source[..len].contains(|char: char| char.is_ascii_alphabetic() || char == '_') {
+    return 0;
+  }
+  len
+}
+
+/// The offset of the `}` closing an interpolation, skipping nested braces and strings.
+fn closing_brace(source: &str) -> Option<usize> {
+  let mut depth = 0usize;
+  let mut quote = None;
+  let mut escaped = false;
+  for (i, byte) in source.bytes().enumerate() {
+    match quote {
+      Some(_) if escaped => escaped = false,
+      Some(_) if byte == b'\\' => escaped = true,
+      Some(open) if byte == open => quote = None,
+      Some(_) => {}
+      None => match byte {
+        b'"' | b'\'' | b'`' => quote = Some(byte),
+        b'{' => depth += 1,
+        b'}' if depth == 0 => return Some(i),
+        b'}' => depth -= 1,
+        _ => {}
+      },
+    }
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    PugLexer,
+    PugTokenKind::{
+      self, AttributeName, AttributeValue, Class, Colon, Comment, Dot, EndAttributes, Id, Indent,
+      Interpolation, Newline, Outdent, Pipe, SlashClose, StartAttributes, Tag, Text,
+    },
+  };
+
+  fn tokens(source: &str) -> Vec<(PugTokenKind, &str)> {
+    let (tokens, errors) = PugLexer::new(source, 0).lex();
+    assert!(errors.is_empty(), "{errors:?}");
+    tokens.iter().map(|token| (token.kind, token.value)).collect()
+  }
+
+  fn errors(source: &str) -> Vec<String> {
+    let (_, errors) = PugLexer::new(source, 0).lex();
+    errors.iter().map(ToString::to_string).collect()
+  }
+
+  #[test]
+  fn tag_heads() {
+    assert_eq!(
+      tokens("a.link#top(href=\"/\", :class='{ active }' disabled)/"),
+      vec![
+        (Tag, "a"),
+        (Class, "link"),
+        (Id, "top"),
+        (StartAttributes, ""),
+        (AttributeName, "href"),
+        (AttributeValue, "/"),
+        (AttributeName, ":class"),
+        (AttributeValue, "{ active }"),
+        (AttributeName, "disabled"),
+        (EndAttributes, ""),
+        (SlashClose, ""),
+      ]
+    );
+  }
+
+  #[test]
+  fn multiline_attributes() {
+    assert_eq!(
+      tokens("input(\n  v-model=\"name\"\n  tabindex=1\n  :[key]=\"value\"\n)\np"),
+      vec![
+        (Tag, "input"),
+        (StartAttributes, ""),
+        (AttributeName, "v-model"),
+        (AttributeValue, "name"),
+        (AttributeName, "tabindex"),
+        (AttributeValue, "1"),
+        (AttributeName, ":[key]"),
+        (AttributeValue, "value"),
+        (EndAttributes, ""),
+        (Newline, ""),
+        (Tag, "p"),
+      ]
+    );
+  }
+
+  #[test]
+  fn indentation() {
+    assert_eq!(
+      tokens("ul\n  li\n    a\n\n  li\np"),
+      vec![
+        (Tag, "ul"),
+        (Indent, "  "),
+        (Tag, "li"),
+        (Indent, "    "),
+        (Tag, "a"),
+        (Outdent, ""),
+        (Tag, "li"),
+        (Outdent, ""),
+        (Tag, "p"),
+      ]
+    );
+  }
+
+  #[test]
+  fn text_and_interpolations() {
+    assert_eq!(
+      tokens("p Hi #{ name }!\n| {{ count }} left\nspan= total"),
+      vec![
+        (Tag, "p"),
+        (Text, "Hi "),
+        (Interpolation, " name "),
+        (Text, "!"),
+        (Newline, ""),
+        (Pipe, ""),
+        (Interpolation, " count "),
+        (Text, " left"),
+        (Newline, ""),
+        (Tag, "span"),
+        (Interpolation, "total"),
+      ]
+    );
+  }
+
+  #[test]
+  fn text_blocks_and_comments() {
+    assert_eq!(
+      tokens("p.\n  Hello\n    world\n//- note\n  more\nli: a Home"),
+      vec![
+        (Tag, "p"),
+        (Dot, ""),
+        (Text, "Hello\n"),
+        (Text, "  world"),
+        (Newline, ""),
+        (Comment, " note\n  more"),
+        (Newline, ""),
+        (Tag, "li"),
+        (Colon, ""),
+        (Tag, "a"),
+        (Text, "Home"),
+      ]
+    );
+  }
+
+  #[test]
+  fn reports_errors() {
+    assert_eq!(
+      errors("div\n  p\n\tp"),
+      ["Invalid indentation, you can use tabs or spaces but not both."]
+    );
+    assert_eq!(errors("div\n    p\n  p"), ["Inconsistent indentation."]);
+    assert_eq!(
+      errors("each item in items\n  p= item"),
+      ["`each` is not supported in Vue templates."]
+    );
+    assert_eq!(errors("p(class=\"a"), ["Unterminated string."]);
+    assert_eq!(errors("p(class=\"a\""), ["Unclosed attribute list."]);
+    assert_eq!(errors("p #{ name"), ["Unclosed interpolation."]);
+  }
+}
@@ -0,0 +1,497 @@
+//! Pug templates, for `<template lang="pug">`.
+//!
+//! [`parse_pug`] turns the content of the template into a tree of [`PugNode`]s, with all spans
+//! pointing into the Pug source. The V-tree and the JSX transform are built from this tree the
+//! same way as from an HTML template, so everything downstream handles Pug components unchanged.
+//!
+//! Only the Pug subset which maps onto Vue templates is supported: tags with class / id
+//! shorthands and attribute lists, nesting by indentation, block expansion (`li: a`), piped text,
+//! text blocks, `#{}` / `{{ }}` interpolations, `= expr` buffered code and comments. Logic and
+//! composition (`if`, `each`, `mixin`, `include`, `- code`, ...) are reported and skipped, as Vue
+//! templates use directives for those.
+
+mod lexer;
+
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+
+pub use lexer::{PugLexer, PugToken, PugTokenKind};
+
+pub struct PugReturn<'s> {
+  pub nodes: Vec<PugNode<'s>>,
+  pub errors: Vec<OxcDiagnostic>,
+}
+
+#[derive(Debug)]
+pub enum PugNode<'s> {
+  Element(PugElement<'s>),
+  Text(PugText<'s>),
+  /// `#{ expr }`, `{{ expr }}` or the buffered code of `p= expr`.
+  Interpolation(PugInterpolation<'s>),
+  Comment(PugComment<'s>),
+}
+
+impl PugNode<'_> {
+  #[must_use]
+  pub const fn span(&self) -> Span {
+    match self {
+      Self::Element(element) => element.span,
+      Self::Text(text) => text.span,
+      Self::Interpolation(interpolation) => interpolation.span,
+      Self::Comment(comment) => comment.span,
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct PugElement<'s> {
+  /// The tag name, `div` for an element written with only shorthands, like `.card`.
+  pub name: &'s str,
+  /// The span of the tag name, which is empty for an implicit `div`.
+  pub name_span: Span,
+  /// The shorthands and the attribute list, in source order.
+  pub attributes: Vec<PugAttribute<'s>>,
+  pub children: Vec<PugNode<'s>>,
+  /// Ends with `/`, like `img/`.
+  pub self_closing: bool,
+  /// The tag name, the shorthands and the attribute list.
+  pub head_span: Span,
+  /// The head and the children.
+  pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct PugAttribute<'s> {
+  /// The name as written, or `class` and `id` for the `.class` and `#id` shorthands.
+  pub name: &'s str,
+  /// The span of the name, or of the `.` / `#` of a shorthand.
+  pub name_span: Span,
+  pub value: Option<PugAttributeValue<'s>>,
+  pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct PugAttributeValue<'s> {
+  /// The value without its quotes.
+  pub value: &'s str,
+  pub value_span: Span,
+  /// The value with its quotes.
+  pub span: Span,
+}
+
+impl PugAttributeValue<'_> {
+  /// Whether the value is a string, the others being JavaScript expressions like `tabindex=1`.
+  #[must_use]
+  pub fn is_quoted(&self) -> bool {
+    self.span != self.value_span
+  }
+}
+
+#[derive(Debug)]
+pub struct PugText<'s> {
+  pub text: &'s str,
+  pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct PugInterpolation<'s> {
+  pub expression: &'s str,
+  pub expression_span: Span,
+  pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct PugComment<'s> {
+  /// The text after `//` or `//-`, including the nested lines.
+  pub value: &'s str,
+  pub span: Span,
+}
+
+/// A directive attribute split into its parts, see [`PugAttribute::directive`].
+#[derive(Debug)]
+pub struct PugDirectiveName<'s> {
+  /// The name without `v-`, with the shorthands expanded: `bind` for `:` and `.`, `on` for `@`
+  /// and `slot` for `#`.
+  pub name: &'s str,
+  /// The span of the name as written, which is the shorthand character for shorthands.
+  pub name_span: Span,
+  pub argument: Option<PugDirectiveArgument<'s>>,
+  /// The modifiers without their leading `.`.
+  pub modifiers: Vec<(&'s str, Span)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PugDirectiveArgument<'s> {
+  /// The argument, without the brackets of a dynamic argument.
+  pub name: &'s str,
+  pub span: Span,
+  /// `v-bind:[key]`
+  pub dynamic: bool,
+}
+
+impl<'s> PugAttribute<'s> {
+  /// Split the name of a directive, like `v-on:click.stop` or `#default`. Returns [`None`] for
+  /// plain attributes and the `.class` / `#id` shorthands.
+  #[must_use]
+  pub fn directive(&self) -> Option<PugDirectiveName<'s>> {
+    let raw = self.name;
+    if raw.len() as u32 != self.name_span.size() {
+      // `.class` and `#id`
+      return None;
+    }
+    let start = self.name_span.start;
+
+    let (name, name_len, has_argument) = match raw.as_bytes().first() {
+      _ if raw.starts_with("v-") => {
+        let len = raw[2..].find([':', '.']).map_or(raw.len(), |len| 2 + len);
+        (&raw[2..len], len, raw[len..].starts_with(':'))
+      }
+      Some(b':' | b'.') => ("bind", 1, true),
+      Some(b'@') => ("on", 1, true),
+      Some(b'#') => ("slot", 1, true),
+      _ => return None,
+    };
+    if name.is_empty() {
+      return None;
+    }
+
+    // Skip the `:` after `v-bind`
+    let mut pos = name_len + usize::from(has_argument && raw.starts_with("v-"));
+    let mut argument = None;
+    if has_argument {
+      let rest = &raw[pos..];
+      let len = if rest.starts_with('[') {
+        rest.find(']').map_or(rest.len(), |close| close + 1)
+      } else {
+        rest.find('.').unwrap_or(rest.len())
+      };
+      let dynamic = len >= 2 && rest.starts_with('[') && rest[..len].ends_with(']');
+      let (name, name_start) =
+        if dynamic { (&rest[1..len - 1], pos + 1) } else { (&rest[..len], pos) };
+      if !name.is_empty() {
+        let span = Span::sized(start + name_start as u32, name.len() as u32);
+        argument = Some(PugDirectiveArgument { name, span, dynamic });
+      }
+      pos += len;
+    }
+
+    let mut modifiers = vec![];
+    for modifier in raw[pos..].split('.').skip(1) {
+      modifiers.push((modifier, Span::sized(start + pos as u32 + 1, modifier.len() as u32)));
+      pos += 1 + modifier.len();
+    }
+    Some(PugDirectiveName {
+      name,
+      name_span: Span::sized(start, name_len as u32),
+      argument,
+      modifiers,
+    })
+  }
+}
+
+/// Parse the Pug `source`, which starts at `offset` of the SFC. `delimiters` are the Vue
+/// interpolation delimiters, recognized in text besides `#{` and `}`.
+#[must_use]
+pub fn parse_pug<'s>(source: &'s str, offset: u32, delimiters: (&str, &str)) -> PugReturn<'s> {
+  let (tokens, errors) =
+    PugLexer::new(source, offset).with_delimiters(delimiters.0, delimiters.1).lex();
+  let mut parser = PugParser { tokens: tokens.into_iter().peekable(), errors };
+  let mut nodes = vec![];
+  while parser.tokens.peek().is_some() {
+    nodes.append(&mut parser.block());
+  }
+  PugReturn { nodes, errors: parser.errors }
+}
+
+struct PugParser<'s> {
+  tokens: std::iter::Peekable<std::vec::IntoIter<PugToken<'s>>>,
+  errors: Vec<OxcDiagnostic>,
+}
+
+const fn is_head(kind: PugTokenKind) -> bool {
+  matches!(
+    kind,
+    PugTokenKind::Id
+      | PugTokenKind::Class
+      | PugTokenKind::StartAttributes
+      | PugTokenKind::SlashClose
+  )
+}
+
+const fn is_element_start(kind: PugTokenKind) -> bool {
+  matches!(
+    kind,
+    PugTokenKind::Tag | PugTokenKind::Id | PugTokenKind::Class | PugTokenKind::StartAttributes
+  )
+}
+
+impl<'s> PugParser<'s> {
+  /// The nodes until the [`PugTokenKind::Outdent`] closing the block, which is consumed.
+  fn block(&mut self) -> Vec<PugNode<'s>> {
+    let mut nodes = vec![];
+    while let Some(token) = self.tokens.next() {
+      match token.kind {
+        PugTokenKind::Outdent => break,
+        // Nested lines without a parent element, like after piped text
+        PugTokenKind::Indent => nodes.append(&mut self.block()),
+        PugTokenKind::Pipe => self.inline(&mut nodes),
+        PugTokenKind::Text | PugTokenKind::Interpolation => {
+          nodes.push(text_node(token));
+          self.inline(&mut nodes);
+        }
+        PugTokenKind::Comment => {
+          nodes.push(PugNode::Comment(PugComment { value: token.value, span: token.span }));
+        }
+        kind if is_element_start(kind) => nodes.push(PugNode::Element(self.element(token))),
+        _ => {}
+      }
+    }
+    nodes
+  }
+
+  /// The text and interpolations until the end of the line.
+  fn inline(&mut self, nodes: &mut Vec<PugNode<'s>>) {
+    while let Some(token) = self
+      .tokens
+      .next_if(|token| matches!(token.kind, PugTokenKind::Text | PugTokenKind::Interpolation))
+    {
+      nodes.push(text_node(token));
+    }
+  }
+
+  fn element(&mut self, first: PugToken<'s>) -> PugElement<'s> {
+    let (name, name_span) = if first.kind == PugTokenKind::Tag {
+      (first.value, first.span)
+    } else {
+      ("div", Span::empty(first.span.start))
+    };
+    let mut element = PugElement {
+      name,
+      name_span,
+      attributes: vec![],
+      children: vec![],
+      self_closing: false,
+      head_span: first.span,
+      span: first.span,
+    };
+
+    let mut next = if first.kind == PugTokenKind::Tag { self.next_head() } else { Some(first) };
+    while let Some(token) = next {
+      element.head_span.end = match token.kind {
+        PugTokenKind::Id | PugTokenKind::Class => {
+          let name = if token.kind == PugTokenKind::Id { "id" } else { "class" };
+          let value = PugAttributeValue {
+            value: token.value,
+            value_span: token.value_span,
+            span: token.value_span,
+          };
+          element.attributes.push(PugAttribute {
+            name,
+            name_span: Span::sized(token.span.start, 1),
+            value: Some(value),
+            span: token.span,
+          });
+          token.span.end
+        }
+        PugTokenKind::StartAttributes => self.attributes(&mut element.attributes, token.span.end),
+        _ => {
+          element.self_closing = true;
+          token.span.end
+        }
+      };
+      next = self.next_head();
+    }
+
+    match self.tokens.peek().map(|token| token.kind) {
+      Some(PugTokenKind::Colon) => {
+        self.tokens.next();
+        if let Some(token) = self.tokens.next_if(|token| is_element_start(token.kind)) {
+          element.children.push(PugNode::Element(self.element(token)));
+        }
+      }
+      Some(PugTokenKind::Dot) => {
+        self.tokens.next();
+        self.inline(&mut element.children);
+      }
+      _ => self.inline(&mut element.children),
+    }
+    if self.tokens.next_if(|token| token.kind == PugTokenKind::Indent).is_some() {
+      element.children.append(&mut self.block());
+    }
+
+    element.span.end =
+      element.children.last().map_or(element.head_span.end, |child| child.span().end);
+    element
+  }
+
+  fn next_head(&mut self) -> Option<PugToken<'s>> {
+    self.tokens.next_if(|token| is_head(token.kind))
+  }
+
+  /// The attributes of an attribute list, returning the end of the list.
+  fn attributes(&mut self, attributes: &mut Vec<PugAttribute<'s>>, mut end: u32) -> u32 {
+    while let Some(token) = self.tokens.next_if(|token| {
+      matches!(token.kind, PugTokenKind::AttributeName | PugTokenKind::EndAttributes)
+    }) {
+      end = token.span.end;
+      if token.kind == PugTokenKind::EndAttributes {
+        break;
+      }
+
+      let mut attribute = PugAttribute {
+        name: token.value,
+        name_span: token.value_span,
+        value: None,
+        span: token.span,
+      };
+      if let Some(value) = self.tokens.next_if(|token| token.kind == PugTokenKind::AttributeValue) {
+        end = value.span.end;
+        attribute.span.end = value.span.end;
+        attribute.value = Some(PugAttributeValue {
+          value: value.value,
+          value_span: value.value_span,
+          span: value.span,
+        });
+      }
+      // Skipped, as the directive without its value would be lowered as a shorthand, like `:a`
+      if let Some(value) = &attribute.value
+        && !value.is_quoted()
+        && attribute.directive().is_some()
+      {
+        self.errors.push(
+          OxcDiagnostic::error("The value of a directive must be a quoted string.")
+            .with_label(value.span),
+        );
+        continue;
+      }
+      attributes.push(attribute);
+    }
+    end
+  }
+}
+
+fn text_node(token: PugToken<'_>) -> PugNode<'_> {
+  if token.kind == PugTokenKind::Interpolation {
+    PugNode::Interpolation(PugInterpolation {
+      expression: token.value,
+      expression_span: token.value_span,
+      span: token.span,
+    })
+  } else {
+    PugNode::Text(PugText { text: token.value, span: token.span })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use oxc_span::Span;
+
+  use super::{PugElement, PugNode, parse_pug};
+
+  fn element<'s>(node: &'s PugNode<'s>) -> &'s PugElement<'s> {
+    let PugNode::Element(element) = node else { panic!("expected an element, got {node:?}") };
+    element
+  }
+
+  #[test]
+  fn nests_elements_by_indentation() {
+    let source = "ul#list.items\n  li: a(href=\"/\") Home\n  li\n    | Hello #{ name }\nfooter";
+    let ret = parse_pug(source, 10, ("{{", "}}"));
+    assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+    assert_eq!(ret.nodes.len(), 2);
+
+    let list = element(&ret.nodes[0]);
+    assert_eq!(list.name, "ul");
+    assert_eq!(list.head_span, Span::new(10, 23));
+    let attributes: Vec<_> = list
+      .attributes
+      .iter()
+      .map(|attribute| (attribute.name, attribute.value.as_ref().unwrap().value))
+      .collect();
+    assert_eq!(attributes, [("id", "list"), ("class", "items")]);
+    assert_eq!(list.children.len(), 2);
+    assert_eq!(list.span.end, 10 + source.find("\nfooter").unwrap() as u32);
+
+    let link = element(&element(&list.children[0]).children[0]);
+    assert_eq!(link.name, "a");
+    assert_eq!(
+      link.attributes[0].value.as_ref().unwrap().span.source_text(&format!("{:10}{source}", "")),
+      "\"/\""
+    );
+    assert!(matches!(&link.children[..], [PugNode::Text(text)] if text.text == "Home"));
+
+    let item = element(&list.children[1]);
+    assert!(matches!(
+      &item.children[..],
+      [PugNode::Text(text), PugNode::Interpolation(interpolation)]
+        if text.text == "Hello " && interpolation.expression == " name "
+    ));
+    assert_eq!(element(&ret.nodes[1]).name, "footer");
+  }
+
+  #[test]
+  fn implicit_div_and_self_closing() {
+    let ret = parse_pug(".card(v-if=\"show\")\n  img(:src=\"url\")/", 0, ("{{", "}}"));
+    let card = element(&ret.nodes[0]);
+    assert_eq!((card.name, card.name_span), ("div", Span::empty(0)));
+    let image = element(&card.children[0]);
+    assert!(image.self_closing);
+    assert_eq!(
+      image.head_span.source_text(".card(v-if=\"show\")\n  img(:src=\"url\")/"),
+      "img(:src=\"url\")/"
+    );
+  }
+
+  #[test]
+  fn splits_directive_names() {
+    let source = "p(v-on:click.stop.prevent=\"go\" :[key]=\"value\" #item=\"{ row }\" v-model.trim=\"name\" .checked=\"on\" class=\"a\")";
+    let ret = parse_pug(source, 0, ("{{", "}}"));
+    let directives: Vec<_> = element(&ret.nodes[0])
+      .attributes
+      .iter()
+      .map(|attribute| {
+        attribute.directive().map(|directive| {
+          (
+            directive.name,
+            directive.name_span.source_text(source),
+            directive
+              .argument
+              .map(|argument| (argument.name, argument.span.source_text(source), argument.dynamic)),
+            directive
+              .modifiers
+              .iter()
+              .map(|&(modifier, span)| (modifier, span.source_text(source)))
+              .collect::<Vec<_>>(),
+          )
+        })
+      })
+      .collect();
+    assert_eq!(
+      directives,
+      [
+        Some((
+          "on",
+          "v-on",
+          Some(("click", "click", false)),
+          vec![("stop", "stop"), ("prevent", "prevent")]
+        )),
+        Some(("bind", ":", Some(("key", "key", true)), vec![])),
+        Some(("slot", "#", Some(("item", "item", false)), vec![])),
+        Some(("model", "v-model", None, vec![("trim", "trim")])),
+        Some(("bind", ".", Some(("checked", "checked", false)), vec![])),
+        None,
+      ]
+    );
+  }
+
+  #[test]
+  fn reports_unquoted_directive_values() {
+    let ret = parse_pug("p(v-if=show :a=b c='d' tabindex=1)", 0, ("{{", "}}"));
+    assert_eq!(ret.errors.len(), 2);
+    assert_eq!(ret.errors[1].labels.as_ref().unwrap()[0].offset(), 15);
+    let attributes = &element(&ret.nodes[0]).attributes;
+    let names: Vec<_> = attributes.iter().map(|attribute| attribute.name).collect();
+    assert_eq!(names, ["c", "tabindex"]);
+    assert_eq!(attributes[1].value.as_ref().unwrap().value, "1");
+  }
+}
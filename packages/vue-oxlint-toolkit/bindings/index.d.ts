@@ -25,6 +25,28 @@ export interface NativeRange {
   end: number
 }
 
+/** The source and flags of a JS `RegExp`. */
+export interface NativeRegExp {
+  source: string
+  flags: string
+}
+
+export interface NativeTransformOptions {
+  /** The Vue major version whose template semantics are followed, defaults to `3`. */
+  vueVersion?: 2 | 3
+  /** Tag name prefixes of custom elements, e.g. `sl-`. */
+  customElementPrefixes?: Array<string>
+  /**
+   * Regex patterns matching custom element tag names, in Rust `regex` syntax (no lookaround or
+   * backreferences).
+   */
+  customElementPatterns?: Array<NativeRegExp>
+  /** Interpolation delimiters, defaults to `['{{', '}}']`. */
+  delimiters?: [string, string]
+  /** Whether the source is a VitePress Markdown page rather than an SFC. */
+  markdown?: boolean
+}
+
 export interface NativeTransformResult {
   sourceText: string
   scriptKind: 'jsx' | 'tsx'
@@ -32,6 +54,35 @@ export interface NativeTransformResult {
   irregularWhitespaces: Array<NativeRange>
   errors: Array<NativeDiagnostic>
   mappings: Array<NativeMapping>
+  /**
+   * The `<style>` and custom blocks (like `<i18n>` or `<docs>`), for linting them with the tools
+   * of their own languages.
+   */
+  virtualDocuments: Array<NativeVirtualDocument>
 }
 
-export declare function transformJsx(source: string): NativeTransformResult
+export interface NativeVirtualDocument {
+  /** The tag name of the block, e.g. `style` or `i18n`. */
+  tag: string
+  /** The `lang` attribute, defaulting to `css` for `<style>` and `txt` for custom blocks. */
+  lang: string
+  content: string
+  /** The `src` attribute of an external block, whose content is empty. */
+  src?: string
+  scoped: boolean
+  /** The injected name of a CSS Modules block, e.g. `$style`. */
+  module?: string
+  mappings: Array<NativeMapping>
+}
+
+/** Build the component manifest of a Vue SFC, serialized as JSON. */
+export declare function componentManifest(source: string): string
+
+/**
+ * Transform a Vue SFC, or a VitePress Markdown page, into JSX.
+ *
+ * Invalid options (an unsupported Vue version, a custom element pattern which is not a valid
+ * regex, or delimiters which are not two non-empty strings) are reported in `errors` and fall
+ * back to their defaults.
+ */
+export declare function transformJsx(source: string, options?: NativeTransformOptions | undefined | null): NativeTransformResult
@@ -1,6 +1,14 @@
 import type { Comment, Diagnostic, Range } from '@oxlint/plugins'
-import type { NativeMapping, NativeRange, NativeTransformResult } from '../bindings'
-import { transformJsx as nativeTransformJsx } from '../bindings'
+import type {
+  NativeMapping,
+  NativeRange,
+  NativeTransformOptions,
+  NativeTransformResult,
+} from '../bindings'
+import {
+  componentManifest as nativeComponentManifest,
+  transformJsx as nativeTransformJsx,
+} from '../bindings'
 
 export interface Mapping {
   virtualStart: number
@@ -16,6 +24,53 @@ export interface ToolkitTransformResult {
   irregularWhitespaces: Range[]
   errors: Diagnostic[]
   mappings: Mapping[]
+  /**
+   * The `<style>` and custom blocks (like `<i18n>` or `<docs>`), for linting them with the tools
+   * of their own languages.
+   */
+  virtualDocuments: VirtualDocument[]
+}
+
+export interface TransformOptions {
+  /**
+   * The Vue major version whose template semantics are followed, defaults to `3`. Vue 2 (2.6 and
+   * 2.7) enables `slot-scope`, `.sync` and `<template functional>`.
+   */
+  vueVersion?: 2 | 3
+  /**
+   * Tags which are custom elements (web components) rather than Vue components, like Vue's
+   * `compilerOptions.isCustomElement`. Strings are tag name prefixes (`'sl-'`).
+   *
+   * Regular expressions are matched natively with Rust `regex` syntax, which has no lookaround
+   * (`(?=`, `(?<!`) or backreferences. The `i`, `m`, `s` and `u` flags are supported. Patterns
+   * using anything else (like the `y` or `v` flags) are reported in `errors` and ignored.
+   */
+  isCustomElement?: (string | RegExp)[]
+  /**
+   * Interpolation delimiters, like Vue's `compilerOptions.delimiters`, defaults to `['{{', '}}']`.
+   * A component's own `delimiters` option (also via `defineOptions`) takes precedence.
+   */
+  delimiters?: [string, string]
+  /**
+   * Whether `source` is a VitePress Markdown page. Its `<script>` and `<style>` blocks are hoisted
+   * and the rest becomes the template, with locations still pointing into the page.
+   */
+  markdown?: boolean
+}
+
+export interface VirtualDocument {
+  /** The tag name of the block, e.g. `style` or `i18n`. */
+  tag: string
+  /** The `lang` attribute, defaulting to `css` for `<style>` and `txt` for custom blocks. */
+  lang: string
+  content: string
+  /** The `src` attribute of an external block, whose content is empty. */
+  src?: string
+  scoped: boolean
+  /** The injected name of a CSS Modules block, e.g. `$style`. */
+  module?: string
+  /** Maps `content` back to the SFC. */
+  mappings: Mapping[]
 }
 
 export interface ParseResult {
@@ -24,10 +79,26 @@ export interface ParseResult {
   transform: ToolkitTransformResult
 }
 
+interface ManifestItem {
+  name: string
+  description: string | null
+  start: number
+  end: number
+}
+
+export interface ComponentManifest {
+  name: string | null
+  props: (ManifestItem & { type: string | null; required: boolean; default: string | null })[]
+  emits: (ManifestItem & { payload: string[] })[]
+  slots: (ManifestItem & { type: string | null; props: { name: string; expression: string }[] })[]
+  models: (ManifestItem & { type: string | null })[]
+  exposes: ManifestItem[]
+}
+
 export declare function parse(path: string, source: string, options?: {}): ParseResult
 
-export function transformJsx(source: string): ToolkitTransformResult {
-  const result: NativeTransformResult = nativeTransformJsx(source)
+export function transformJsx(source: string, options?: TransformOptions): ToolkitTransformResult {
+  const result: NativeTransformResult = nativeTransformJsx(source, toNativeOptions(options))
   const locator = createLocator(source)
   const virtualLocator = createLocator(result.sourceText)
 
@@ -48,6 +119,51 @@ export function transformJsx(source: string): ToolkitTransformResult {
       loc: toLocation(error, locator),
     })),
     mappings: result.mappings.map((mapping) => toMapping(mapping, locator, virtualLocator)),
+    virtualDocuments: result.virtualDocuments.map((document) => {
+      const documentLocator = createLocator(document.content)
+
+      return {
+        tag: document.tag,
+        lang: document.lang,
+        content: document.content,
+        src: document.src,
+        scoped: document.scoped,
+        module: document.module,
+        mappings: document.mappings.map((mapping) => toMapping(mapping, locator, documentLocator)),
+      }
+    }),
+  }
+}
+
+export function componentManifest(source: string): ComponentManifest {
+  const manifest: ComponentManifest = JSON.parse(nativeComponentManifest(source))
+  const locator = createLocator(source)
+
+  for (const item of [
+    ...manifest.props,
+    ...manifest.emits,
+    ...manifest.slots,
+    ...manifest.models,
+    ...manifest.exposes,
+  ]) {
+    item.start = locator.toIndex(item.start)
+    item.end = locator.toIndex(item.end)
+  }
+
+  return manifest
+}
+
+function toNativeOptions(options: TransformOptions = {}): NativeTransformOptions {
+  const isCustomElement = options.isCustomElement ?? []
+
+  return {
+    vueVersion: options.vueVersion,
+    customElementPrefixes: isCustomElement.filter((item) => typeof item === 'string'),
+    customElementPatterns: isCustomElement
+      .filter((item) => item instanceof RegExp)
+      .map((pattern) => ({ source: pattern.source, flags: pattern.flags })),
+    delimiters: options.delimiters,
+    markdown: options.markdown,
   }
 }
 
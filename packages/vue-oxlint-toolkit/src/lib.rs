@@ -1,7 +1,13 @@
 #![deny(clippy::all)]
 
+use oxc_allocator::Allocator;
 use oxc_ast::ast::CommentKind;
-use vue_oxlint_jsx::VueJsxCodegen;
+use oxc_diagnostics::OxcDiagnostic;
+use regex::Regex;
+use vue_oxlint_jsx::{
+  ComponentManifest, CustomElements, Delimiters, Mapping, MarkdownSfc, VirtualDocument,
+  VueJsxCodegen, VueJsxParser, VueVersion,
+};
 
 use napi_derive::napi;
 
@@ -35,6 +41,31 @@ pub struct NativeMapping {
   pub original_end: u32,
 }
 
+/// The source and flags of a JS `RegExp`.
+#[napi(object)]
+pub struct NativeRegExp {
+  pub source: String,
+  pub flags: String,
+}
+
+#[napi(object)]
+#[derive(Default)]
+pub struct NativeTransformOptions {
+  /// The Vue major version whose template semantics are followed, defaults to `3`.
+  #[napi(ts_type = "2 | 3")]
+  pub vue_version: Option<u32>,
+  /// Tag name prefixes of custom elements, e.g. `sl-`.
+  pub custom_element_prefixes: Option<Vec<String>>,
+  /// Regex patterns matching custom element tag names, in Rust `regex` syntax (no lookaround or
+  /// backreferences).
+  pub custom_element_patterns: Option<Vec<NativeRegExp>>,
+  /// Interpolation delimiters, defaults to `['{{', '}}']`.
+  #[napi(ts_type = "[string, string]")]
+  pub delimiters: Option<Vec<String>>,
+  /// Whether the source is a VitePress Markdown page rather than an SFC.
+  pub markdown: Option<bool>,
+}
+
 #[napi(object)]
 pub struct NativeTransformResult {
   pub source_text: String,
@@ -44,13 +75,55 @@ pub struct NativeTransformResult {
   pub irregular_whitespaces: Vec<NativeRange>,
   pub errors: Vec<NativeDiagnostic>,
   pub mappings: Vec<NativeMapping>,
+  /// The `<style>` and custom blocks (like `<i18n>` or `<docs>`), for linting them with the tools
+  /// of their own languages.
+  pub virtual_documents: Vec<NativeVirtualDocument>,
 }
 
+#[napi(object)]
+pub struct NativeVirtualDocument {
+  /// The tag name of the block, e.g. `style` or `i18n`.
+  pub tag: String,
+  /// The `lang` attribute, defaulting to `css` for `<style>` and `txt` for custom blocks.
+  pub lang: String,
+  pub content: String,
+  /// The `src` attribute of an external block, whose content is empty.
+  pub src: Option<String>,
+  pub scoped: bool,
+  /// The injected name of a CSS Modules block, e.g. `$style`.
+  pub module: Option<String>,
+  pub mappings: Vec<NativeMapping>,
+}
+
+/// Transform a Vue SFC, or a VitePress Markdown page, into JSX.
+///
+/// Invalid options (an unsupported Vue version, a custom element pattern which is not a valid
+/// regex, or delimiters which are not two non-empty strings) are reported in `errors` and fall
+/// back to their defaults.
 #[napi]
 #[must_use]
 #[allow(clippy::needless_pass_by_value, reason = "N-API owns string arguments at the boundary.")]
-pub fn transform_jsx(source: String) -> NativeTransformResult {
-  let ret = VueJsxCodegen::new(&source).build();
+pub fn transform_jsx(
+  source: String,
+  options: Option<NativeTransformOptions>,
+) -> NativeTransformResult {
+  let options = options.unwrap_or_default();
+  let mut errors = vec![];
+  let vue_version = vue_version(options.vue_version, &mut errors);
+  let delimiters = delimiters(options.delimiters.as_deref(), &mut errors);
+  let markdown = options.markdown.unwrap_or_default().then(|| MarkdownSfc::new(&source));
+  let custom_elements = custom_elements(options, &mut errors);
+  let mut ret =
+    VueJsxCodegen::new(markdown.as_ref().map_or(source.as_str(), |sfc| sfc.source_text.as_str()))
+      .with_vue_version(vue_version)
+      .with_custom_elements(custom_elements)
+      .with_delimiters(delimiters)
+      .build();
+  // The spans of a Markdown page are moved from its SFC back to the page
+  if let Some(sfc) = &markdown {
+    sfc.remap_codegen_return(&mut ret);
+  }
+  errors.extend(ret.errors.iter().map(diagnostic));
   let script_kind = if ret.source_type.is_typescript() { "tsx" } else { "jsx" }.to_string();
 
   NativeTransformResult {
@@ -80,33 +153,133 @@ pub fn transform_jsx(source: String) -> NativeTransformResult {
       .iter()
       .map(|span| NativeRange { start: span.start, end: span.end })
       .collect(),
-    errors: ret
-      .errors
-      .iter()
-      .map(|error| {
-        let (start, end) =
-          error.labels.as_ref().and_then(|labels| labels.first()).map_or((0, 0), |label| {
-            let start = label.offset() as u32;
-            let end = start + label.len() as u32;
-            (start, end)
-          });
-
-        NativeDiagnostic { message: error.message.to_string(), start, end }
-      })
-      .collect(),
-    mappings: ret
-      .mappings
-      .iter()
-      .map(|mapping| NativeMapping {
-        virtual_start: mapping.codegen_span.start,
-        virtual_end: mapping.codegen_span.end,
-        original_start: mapping.original_span.start,
-        original_end: mapping.original_span.end,
-      })
-      .collect(),
+    errors,
+    mappings: ret.mappings.iter().map(mapping).collect(),
+    virtual_documents: ret.virtual_documents.into_iter().map(virtual_document).collect(),
+  }
+}
+
+const fn mapping(mapping: &Mapping) -> NativeMapping {
+  NativeMapping {
+    virtual_start: mapping.codegen_span.start,
+    virtual_end: mapping.codegen_span.end,
+    original_start: mapping.original_span.start,
+    original_end: mapping.original_span.end,
+  }
+}
+
+fn virtual_document(document: VirtualDocument) -> NativeVirtualDocument {
+  NativeVirtualDocument {
+    tag: document.tag,
+    lang: document.lang,
+    content: document.content,
+    src: document.src,
+    scoped: document.scoped,
+    module: document.module,
+    mappings: document.mappings.iter().map(mapping).collect(),
+  }
+}
+
+fn diagnostic(error: &OxcDiagnostic) -> NativeDiagnostic {
+  let (start, end) =
+    error.labels.as_ref().and_then(|labels| labels.first()).map_or((0, 0), |label| {
+      let start = label.offset() as u32;
+      let end = start + label.len() as u32;
+      (start, end)
+    });
+
+  NativeDiagnostic { message: error.message.to_string(), start, end }
+}
+
+/// An invalid option, which has no location in the source.
+fn option_error(errors: &mut Vec<NativeDiagnostic>, message: String) {
+  errors.push(NativeDiagnostic { message, start: 0, end: 0 });
+}
+
+fn vue_version(version: Option<u32>, errors: &mut Vec<NativeDiagnostic>) -> VueVersion {
+  match version {
+    Some(2) => VueVersion::V2,
+    Some(3) | None => VueVersion::V3,
+    Some(version) => {
+      option_error(errors, format!("Unsupported Vue version: {version}"));
+      VueVersion::V3
+    }
+  }
+}
+
+/// Patterns are compiled by the Rust `regex` crate, which has no lookaround or backreferences.
+/// Patterns using them, or flags with no inline equivalent, are reported and skipped.
+fn custom_elements(
+  options: NativeTransformOptions,
+  errors: &mut Vec<NativeDiagnostic>,
+) -> CustomElements {
+  let mut custom_elements = CustomElements::new();
+  for prefix in options.custom_element_prefixes.unwrap_or_default() {
+    custom_elements = custom_elements.with_prefix(prefix);
+  }
+  for pattern in options.custom_element_patterns.unwrap_or_default() {
+    if let Some(pattern) = custom_element_pattern(&pattern, errors) {
+      custom_elements = custom_elements.with_pattern(pattern);
+    }
+  }
+  custom_elements
+}
+
+/// Compile a `RegExp`, with its `i`, `m`, `s` and `u` flags as an inline group like `(?ims)`. `g`
+/// and `d` don't change what matches, while the other flags (`y`, `v`) have no equivalent.
+fn custom_element_pattern(
+  pattern: &NativeRegExp,
+  errors: &mut Vec<NativeDiagnostic>,
+) -> Option<Regex> {
+  let NativeRegExp { source, flags } = pattern;
+  let mut inline_flags = String::new();
+  for flag in flags.chars() {
+    match flag {
+      'i' | 'm' | 's' | 'u' => inline_flags.push(flag),
+      'g' | 'd' => {}
+      _ => {
+        option_error(
+          errors,
+          format!("Unsupported flag `{flag}` of the custom element pattern /{source}/{flags}"),
+        );
+        return None;
+      }
+    }
+  }
+
+  let regex = if inline_flags.is_empty() {
+    Regex::new(source)
+  } else {
+    Regex::new(&format!("(?{inline_flags}){source}"))
+  };
+  regex
+    .map_err(|error| option_error(errors, format!("Invalid custom element pattern: {error}")))
+    .ok()
+}
+
+fn delimiters(delimiters: Option<&[String]>, errors: &mut Vec<NativeDiagnostic>) -> Delimiters {
+  match delimiters {
+    Some([open, close]) if !open.is_empty() && !close.is_empty() => Delimiters::new(open, close),
+    Some(_) => {
+      option_error(errors, "Delimiters must be two non-empty strings".to_string());
+      Delimiters::default()
+    }
+    None => Delimiters::default(),
   }
 }
 
+/// Build the component manifest of a Vue SFC, serialized as JSON.
+#[napi]
+#[must_use]
+#[allow(clippy::needless_pass_by_value, reason = "N-API owns string arguments at the boundary.")]
+pub fn component_manifest(source: String) -> String {
+  let allocator = Allocator::default();
+  let ret = VueJsxParser::new(&allocator, &source).parse();
+  let manifest = ComponentManifest::from_program(&ret.program);
+
+  serde_json::to_string(&manifest).unwrap_or_default()
+}
+
 struct CommentData<'a> {
   value: &'a str,
   start: u32,
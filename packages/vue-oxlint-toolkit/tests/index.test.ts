@@ -1,5 +1,5 @@
 import { it, expect } from 'vite-plus/test'
-import { transformJsx } from '../js'
+import { componentManifest, transformJsx } from '../js'
 
 it('transforms Vue SFCs to generated JSX', () => {
   const source = `<script setup lang="ts">
@@ -95,3 +95,152 @@ it('keeps bogus template comment values', () => {
     },
   })
 })
+
+it('builds component manifests', () => {
+  const source = `<script setup lang="ts">
+const props = defineProps<{
+  /** 标题 */
+  title: string
+  size?: number
+}>()
+defineEmits<{ (e: 'close', reason: string): void }>()
+</script>
+
+<template>
+  <slot name="header" :title="props.title" />
+</template>`
+  const manifest = componentManifest(source)
+
+  expect(manifest.props).toMatchObject([
+    { name: 'title', type: 'string', required: true, description: '标题' },
+    { name: 'size', type: 'number', required: false },
+  ])
+  expect(manifest.props[0].start).toBe(source.indexOf('title: string'))
+  expect(manifest.emits).toMatchObject([{ name: 'close', payload: ['reason: string'] }])
+  expect(manifest.slots).toMatchObject([
+    { name: 'header', props: [{ name: 'title', expression: 'props.title' }] },
+  ])
+})
+
+it('keeps custom elements as intrinsic elements', () => {
+  const source = `<template>
+  <sl-button>Click</sl-button>
+  <ion-content />
+  <my-button />
+</template>`
+  const result = transformJsx(source, { isCustomElement: ['sl-', /^ion-/] })
+
+  expect(result.sourceText).toContain('<sl-button>Click</sl-button>')
+  expect(result.sourceText).toContain('<ion-content></ion-content>')
+  expect(result.sourceText).toContain('<MyButton></MyButton>')
+})
+
+it('reports unsupported custom element patterns', () => {
+  const source = `<template>
+  <ion-content />
+  <sl-button />
+</template>`
+  const result = transformJsx(source, { isCustomElement: [/^(?!my-)ion-/, /^sl-/] })
+
+  expect(result.sourceText).toContain('<sl-button></sl-button>')
+  expect(result.sourceText).toContain('<IonContent></IonContent>')
+  expect(result.errors).toHaveLength(1)
+  expect(result.errors[0].message).toContain('Invalid custom element pattern')
+})
+
+it('maps the flags of custom element patterns', () => {
+  const source = `<template>
+  <ION-content />
+  <X-a />
+  <sl-button />
+</template>`
+  const result = transformJsx(source, { isCustomElement: [/^ion-/i, /^x-.$/ims, /^sl-/y] })
+
+  expect(result.sourceText).toContain('<ION-content></ION-content>')
+  expect(result.sourceText).toContain('<X-a></X-a>')
+  expect(result.sourceText).toContain('<SlButton></SlButton>')
+  expect(result.errors).toHaveLength(1)
+  expect(result.errors[0].message).toContain('Unsupported flag `y`')
+})
+
+it('follows Vue 2 semantics with vueVersion 2', () => {
+  const source = `<template>
+  <MyList>
+    <template slot="item" slot-scope="{ item }">{{ item }}</template>
+  </MyList>
+  <MyDialog :title.sync="title" />
+</template>`
+  const result = transformJsx(source, { vueVersion: 2 })
+
+  expect(result.sourceText).toContain('{{item:({item})=><>{item}</>}}')
+  expect(result.sourceText).toContain('v-model:title={title}')
+  expect(result.errors).toEqual([])
+})
+
+it('parses interpolations with custom delimiters', () => {
+  const source = `<script setup>
+const name = 'Vue'
+</script>
+
+<template>
+  <p>Hello, \${ name }!</p>
+</template>`
+  const result = transformJsx(source, { delimiters: ['${', '}'] })
+
+  expect(result.sourceText).toContain('<p>Hello, {name}!</p>')
+  expect(result.errors).toEqual([])
+})
+
+it('transforms VitePress Markdown pages', () => {
+  const source = `<script setup>
+const count = 0
+</script>
+
+# Count
+
+The count is {{ count }}, not \`{{ hidden }}\`.
+
+\`\`\`vue
+{{ alsoHidden }}
+\`\`\``
+  const result = transformJsx(source, { markdown: true })
+
+  expect(result.sourceText).toContain('const count = 0')
+  expect(result.sourceText).toContain('{count}')
+  expect(result.sourceText).not.toContain('hidden')
+  expect(result.errors).toEqual([])
+
+  const originalStart = source.indexOf('count }}')
+  const virtualStart = result.sourceText.indexOf('{count}') + 1
+
+  expect(result.mappings).toContainEqual({
+    virtualStart,
+    virtualEnd: virtualStart + 'count'.length,
+    originalStart,
+    originalEnd: originalStart + 'count'.length,
+  })
+})
+
+it('splits style and custom blocks into virtual documents', () => {
+  const source = `<template><div /></template>
+
+<style scoped lang="scss">
+.ä { color: red; }
+</style>
+
+<i18n lang="json">{ "hello": "Hello" }</i18n>`
+  const result = transformJsx(source)
+
+  expect(result.errors).toEqual([])
+  expect(result.virtualDocuments).toMatchObject([
+    { tag: 'style', lang: 'scss', scoped: true, content: '\n.ä { color: red; }\n' },
+    { tag: 'i18n', lang: 'json', scoped: false, content: '{ "hello": "Hello" }' },
+  ])
+
+  for (const document of result.virtualDocuments) {
+    const [mapping] = document.mappings
+
+    expect(source.slice(mapping.originalStart, mapping.originalEnd)).toBe(document.content)
+    expect(mapping.virtualEnd - mapping.virtualStart).toBe(document.content.length)
+  }
+})